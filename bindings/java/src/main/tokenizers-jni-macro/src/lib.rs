@@ -0,0 +1,375 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, FnArg, ItemFn, Pat, Meta, Token};
+use syn::punctuated::Punctuated;
+
+/// Expands a plain Rust fn into the `extern "system"` JNI symbol the JVM looks for.
+///
+/// ```ignore
+/// #[jni(package = "co.huggingface.tokenizers.pretokenizers", class = "WhitespacePretokenizer", ptr)]
+/// fn pretokenize(tok: &mut Whitespace, s: String) -> Vec<Token> { ... }
+/// ```
+///
+/// `package`/`class` determine the mangled symbol name
+/// (`Java_co_huggingface_tokenizers_pretokenizers_WhitespacePretokenizer_pretokenize`). The `ptr`
+/// flag marks that the first parameter is resolved by reading the `handle` long field off the
+/// receiving object and `reinterpret_cast`-ing it, instead of being converted from a JNI
+/// argument. Every other parameter is converted via [`crate::convert::FromJava`] and the return
+/// value via [`crate::convert::IntoJava`]; on `Err`, a `Result` return throws a `JNIException`
+/// with the error's `{:?}` message and returns `null` instead of propagating the error value itself.
+#[proc_macro_attribute]
+pub fn jni(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attrs = parse_macro_input!(attr with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let func = parse_macro_input!(item as ItemFn);
+
+    let mut package = None;
+    let mut class = None;
+    let mut has_ptr = false;
+
+    for meta in attrs {
+        match meta {
+            Meta::NameValue(nv) if nv.path.is_ident("package") => {
+                package = Some(lit_str(&nv.value));
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("class") => {
+                class = Some(lit_str(&nv.value));
+            }
+            Meta::Path(p) if p.is_ident("ptr") => {
+                has_ptr = true;
+            }
+            _ => {}
+        }
+    }
+
+    let package = package.expect("#[jni] requires a `package = \"...\"` argument");
+    let class = class.expect("#[jni] requires a `class = \"...\"` argument");
+
+    let mangled_prefix = format!(
+        "Java_{}_{}",
+        package.replace('.', "_"),
+        class
+    );
+    let inner_name = func.sig.ident.clone();
+    let symbol_name = format_ident!("{}_{}", mangled_prefix, inner_name);
+
+    let mut inputs = func.sig.inputs.iter();
+    let ptr_arg = if has_ptr { inputs.next() } else { None };
+    let ptr_ty = ptr_arg.map(|arg| match arg {
+        FnArg::Typed(pat) => pat.ty.clone(),
+        FnArg::Receiver(_) => panic!("#[jni(ptr)] function's first argument must be typed"),
+    });
+
+    let mut arg_names = Vec::new();
+    let mut arg_params = Vec::new();
+    let mut arg_conversions = Vec::new();
+    for (i, arg) in inputs.enumerate() {
+        let FnArg::Typed(pat) = arg else {
+            panic!("#[jni] functions may not take `self`");
+        };
+        let name = match &*pat.pat {
+            Pat::Ident(ident) => ident.ident.clone(),
+            _ => format_ident!("arg{}", i),
+        };
+        let java_name = format_ident!("_java_{}", name);
+        let ty = &pat.ty;
+        let java_ty = jni_param_type(ty);
+        arg_params.push(quote! { #java_name: #java_ty });
+        arg_conversions.push(quote! {
+            let #name: #ty = crate::convert::FromJava::from_java(&mut _env, #java_name);
+        });
+        arg_names.push(name);
+    }
+
+    let body = &func.block;
+    let ret = &func.sig.output;
+    let ret_ty = match ret {
+        syn::ReturnType::Type(_, ty) => (**ty).clone(),
+        syn::ReturnType::Default => syn::parse_quote! { () },
+    };
+    let java_ret_ty = jni_param_type(&ret_ty);
+
+    let handle_prelude = if has_ptr {
+        let ptr_name = ptr_arg_name(ptr_arg);
+        quote! {
+            let #ptr_name: &mut _ = unsafe {
+                crate::glue::reinterpret_cast(
+                    _env.get_field(&_obj, "handle", "J")
+                        .and_then(|v| v.j())
+                        .jexcept(&mut _env, crate::helpers::JNI_EXCEPTION_CLASS),
+                )
+            };
+        }
+    } else {
+        quote! {}
+    };
+
+    let ptr_call_arg = if has_ptr {
+        let name = ptr_arg_name(ptr_arg);
+        quote! { #name, }
+    } else {
+        quote! {}
+    };
+    let _ = ptr_ty;
+
+    let expanded = quote! {
+        #[no_mangle]
+        pub unsafe extern "system" fn #symbol_name<'local>(
+            mut _env: jni::JNIEnv<'local>,
+            _obj: jni::objects::JObject<'local>,
+            #(#arg_params),*
+        ) -> #java_ret_ty {
+            use crate::helpers::JExceptable;
+
+            #handle_prelude
+            #(#arg_conversions)*
+
+            fn #inner_name #ret #body
+
+            let _result = #inner_name(#ptr_call_arg #(#arg_names),*);
+            crate::convert::IntoJava::into_java(_result, &mut _env)
+        }
+    };
+
+    expanded.into()
+}
+
+/// Expands a plain Rust fn taking `(&Tokenizer, ...)` and returning `Result<T, E>` into the
+/// `extern "system"` JNI symbol the JVM looks for, so a contributor adding a new `Tokenizer`
+/// native method doesn't have to re-derive the handle lookup, argument marshalling and
+/// mapped-exception `Result` handling by hand every time.
+///
+/// ```ignore
+/// #[tokenizer_jni(class = "Tokenizer", err_class = "co/huggingface/tokenizers/exceptions/DecodingException")]
+/// fn decode(tok: &tokenizers::Tokenizer, ids: Vec<u32>) -> Result<String, tokenizers::tokenizer::Error> {
+///     tok.decode(&ids, false)
+/// }
+/// ```
+///
+/// The first parameter is always the `&Tokenizer` receiver, reconstituted by reading the
+/// `handle` long field off the receiving object. Every other parameter must be `String` (from a
+/// `JString`) or `Vec<u32>` (from a `jintArray`) — the two argument shapes every native method in
+/// this crate currently needs; extend the parameter-type match inside `tokenizer_jni` as more
+/// shapes are needed. On `Err`, the generated shim throws `err_class` with the error's
+/// `{:?}` message and returns a default/null value instead of propagating the error itself.
+#[proc_macro_attribute]
+pub fn tokenizer_jni(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attrs = parse_macro_input!(attr with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let func = parse_macro_input!(item as ItemFn);
+
+    let mut package = "co.huggingface.tokenizers".to_string();
+    let mut class = None;
+    let mut err_class = None;
+
+    for meta in attrs {
+        match meta {
+            Meta::NameValue(nv) if nv.path.is_ident("package") => {
+                package = lit_str(&nv.value);
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("class") => {
+                class = Some(lit_str(&nv.value));
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("err_class") => {
+                err_class = Some(lit_str(&nv.value));
+            }
+            _ => {}
+        }
+    }
+
+    let class = class.expect("#[tokenizer_jni] requires a `class = \"...\"` argument");
+    let err_class = err_class.expect("#[tokenizer_jni] requires an `err_class = \"...\"` argument");
+
+    let mangled_prefix = format!("Java_{}_{}", package.replace('.', "_"), class);
+    let inner_name = func.sig.ident.clone();
+    let symbol_name = format_ident!("{}_{}", mangled_prefix, inner_name);
+
+    let mut inputs = func.sig.inputs.iter();
+    let recv_arg = inputs.next().expect("#[tokenizer_jni] function must take `&Tokenizer` as its first argument");
+    let recv_name = match recv_arg {
+        FnArg::Typed(pat) => match &*pat.pat {
+            Pat::Ident(ident) => ident.ident.clone(),
+            _ => format_ident!("_tokenizer"),
+        },
+        FnArg::Receiver(_) => panic!("#[tokenizer_jni] functions may not take `self`"),
+    };
+
+    // The Ok type of the fn's `Result<T, E>` return determines both the JNI wire type and how
+    // the success value is converted back into it.
+    let ret_ty = match &func.sig.output {
+        syn::ReturnType::Type(_, ty) => (**ty).clone(),
+        syn::ReturnType::Default => panic!("#[tokenizer_jni] function must return a Result<T, E>"),
+    };
+    let ok_ty = result_ok_type(&ret_ty).expect("#[tokenizer_jni] function must return a Result<T, E>");
+    let ok_ty_name = type_name(&ok_ty).expect("unsupported #[tokenizer_jni] success type");
+    let java_ret_ty = match ok_ty_name.as_str() {
+        "String" => quote! { jni::sys::jstring },
+        other => panic!("#[tokenizer_jni] does not yet support returning `{other}`"),
+    };
+    let ok_to_java = match ok_ty_name.as_str() {
+        "String" => quote! {
+            match _env.new_string(_value) {
+                Ok(s) => s.as_raw(),
+                Err(e) => {
+                    let _ = _env.throw_new(#err_class, format!("{e:?}"));
+                    std::ptr::null_mut()
+                }
+            }
+        },
+        _ => unreachable!(),
+    };
+
+    let mut arg_names = Vec::new();
+    let mut arg_params = Vec::new();
+    let mut arg_conversions = Vec::new();
+    for (i, arg) in inputs.enumerate() {
+        let FnArg::Typed(pat) = arg else {
+            panic!("#[tokenizer_jni] functions may not take `self`");
+        };
+        let name = match &*pat.pat {
+            Pat::Ident(ident) => ident.ident.clone(),
+            _ => format_ident!("arg{}", i),
+        };
+        let java_name = format_ident!("_java_{}", name);
+        let ty_name = type_name(&pat.ty).unwrap_or_default();
+
+        let (java_ty, conversion) = match ty_name.as_str() {
+            "String" => (
+                quote! { jni::objects::JString<'local> },
+                quote! {
+                    let #name: String = match _env
+                        .get_string(&#java_name)
+                        .map_err(|e| format!("{e:?}"))
+                        .and_then(|s| s.to_str().map(|s| s.to_string()).map_err(|e| format!("{e:?}")))
+                    {
+                        Ok(s) => s,
+                        Err(e) => {
+                            let _ = _env.throw_new(#err_class, e);
+                            return std::ptr::null_mut();
+                        }
+                    };
+                },
+            ),
+            "Vec" => (
+                quote! { jni::sys::jintArray },
+                quote! {
+                    let #name: Vec<u32> = {
+                        let _arr = unsafe { jni::objects::JIntArray::from_raw(#java_name) };
+                        match _env.get_array_length(&_arr) {
+                            Ok(_len) => {
+                                let mut _buf = vec![0i32; _len as usize];
+                                if let Err(e) = _env.get_int_array_region(&_arr, 0, &mut _buf) {
+                                    let _ = _env.throw_new(#err_class, format!("{e:?}"));
+                                    return std::ptr::null_mut();
+                                }
+                                _buf.into_iter().map(|x| x as u32).collect()
+                            }
+                            Err(e) => {
+                                let _ = _env.throw_new(#err_class, format!("{e:?}"));
+                                return std::ptr::null_mut();
+                            }
+                        }
+                    };
+                },
+            ),
+            other => panic!("#[tokenizer_jni] does not support argument type `{other}`"),
+        };
+        arg_params.push(quote! { #java_name: #java_ty });
+        arg_conversions.push(conversion);
+        arg_names.push(name);
+    }
+
+    let body = &func.block;
+    let ret = &func.sig.output;
+
+    let expanded = quote! {
+        #[no_mangle]
+        pub unsafe extern "system" fn #symbol_name<'local>(
+            mut _env: jni::JNIEnv<'local>,
+            _obj: jni::objects::JObject<'local>,
+            #(#arg_params),*
+        ) -> #java_ret_ty {
+            let _handle = match get_live_handle(&mut _env, &_obj) {
+                Ok(h) => h,
+                Err(e) => {
+                    let _ = _env.throw_new(#err_class, e);
+                    return std::ptr::null_mut();
+                }
+            };
+            let #recv_name: &tokenizers::Tokenizer = unsafe { &*(_handle as *mut tokenizers::Tokenizer) };
+
+            #(#arg_conversions)*
+
+            fn #inner_name #ret #body
+
+            match #inner_name(#recv_name, #(#arg_names),*) {
+                Ok(_value) => #ok_to_java,
+                Err(e) => {
+                    let _ = _env.throw_new(#err_class, format!("{e:?}"));
+                    std::ptr::null_mut()
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Extracts `T` from a `Result<T, E>` return type.
+fn result_ok_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(p) = ty else { return None };
+    let segment = p.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else { return None };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+fn ptr_arg_name(ptr_arg: Option<&FnArg>) -> proc_macro2::Ident {
+    match ptr_arg {
+        Some(FnArg::Typed(pat)) => match &*pat.pat {
+            Pat::Ident(ident) => ident.ident.clone(),
+            _ => format_ident!("_ptr"),
+        },
+        _ => format_ident!("_ptr"),
+    }
+}
+
+/// Maps a Rust-side parameter/return type to the JNI type the generated `extern "system"`
+/// symbol actually exchanges with the JVM, mirroring the conversions [`FromJava`]/[`IntoJava`]
+/// know how to perform.
+fn jni_param_type(ty: &syn::Type) -> proc_macro2::TokenStream {
+    let name = type_name(ty);
+    match name.as_deref() {
+        Some("String") => quote! { jni::objects::JString<'local> },
+        Some("bool") => quote! { jni::sys::jboolean },
+        Some("i32") => quote! { jni::sys::jint },
+        Some("i64") => quote! { jni::sys::jlong },
+        Some("()") => quote! { () },
+        // Collections and richer domain types (e.g. `Vec<Token>`) round-trip through a plain
+        // `jobject` handle; the matching `IntoJava`/`FromJava` impl does the real conversion.
+        _ => quote! { jni::sys::jobject },
+    }
+}
+
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        syn::Type::Tuple(t) if t.elems.is_empty() => Some("()".to_string()),
+        _ => None,
+    }
+}
+
+fn lit_str(expr: &syn::Expr) -> String {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => s.value(),
+        _ => panic!("expected a string literal"),
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+/// Builds the GPT-2 byte-level bijection: bytes in `0x21..=0x7E`, `0xA1..=0xAC` and `0xAE..=0xFF`
+/// map to themselves (as printable Unicode code points), and every other byte `b` maps to code
+/// point `256 + n`, where `n` counts the previously-unmapped bytes in ascending order. This is
+/// what lets a `ByteLevel` token round-trip through a `String` without touching invalid UTF-8.
+pub fn byte_to_char() -> HashMap<u8, char> {
+    let mut printable: Vec<u8> = Vec::new();
+    printable.extend(0x21..=0x7E);
+    printable.extend(0xA1..=0xAC);
+    printable.extend(0xAE..=0xFF);
+
+    let mut mapping = HashMap::with_capacity(256);
+    let mut n: u32 = 0;
+    for b in 0..=255u8 {
+        let code_point = if printable.contains(&b) {
+            b as u32
+        } else {
+            let code_point = 256 + n;
+            n += 1;
+            code_point
+        };
+        mapping.insert(b, char::from_u32(code_point).expect("byte-level mapping is always valid"));
+    }
+    mapping
+}
+
+pub fn char_to_byte() -> HashMap<char, u8> {
+    byte_to_char().into_iter().map(|(b, c)| (c, b)).collect()
+}
+
+/// Maps raw bytes to their byte-level string representation.
+pub fn encode_bytes(bytes: &[u8], mapping: &HashMap<u8, char>) -> String {
+    bytes.iter().map(|b| mapping[b]).collect()
+}
+
+/// Inverts [`encode_bytes`], failing if `s` contains a character outside the byte-level alphabet.
+pub fn decode_chars(s: &str, mapping: &HashMap<char, u8>) -> Result<Vec<u8>, String> {
+    s.chars()
+        .map(|c| mapping.get(&c).copied().ok_or_else(|| format!("'{c}' is not a byte-level character")))
+        .collect()
+}
@@ -0,0 +1,249 @@
+use jni::objects::{JObject, JObjectArray, JString, JValue};
+use jni::sys::{jint, jobject};
+use jni::JNIEnv;
+
+use tokenizers::tokenizer::Token;
+
+use crate::helpers::{JExceptable, JNI_EXCEPTION_CLASS};
+
+/// Converts a Java-side argument into its Rust equivalent at a JNI boundary.
+///
+/// This is the argument-side counterpart to [`IntoJava`], and exists so the `#[jni]` attribute
+/// macro (see the `tokenizers-jni-macro` crate) can generate argument handling without
+/// special-casing every JNI type by hand.
+pub trait FromJava<'local, J> {
+    fn from_java(env: &mut JNIEnv<'local>, java: J) -> Self;
+}
+
+/// Converts a Rust return value into its Java equivalent at a JNI boundary.
+///
+/// Implementations are expected to throw via [`JExceptable`] on failure and return a safe
+/// default `jobject`/`jstring`/... rather than unwinding across the FFI boundary.
+pub trait IntoJava<'local, J> {
+    fn into_java(self, env: &mut JNIEnv<'local>) -> J;
+}
+
+impl<'local> FromJava<'local, JString<'local>> for String {
+    fn from_java(env: &mut JNIEnv<'local>, java: JString<'local>) -> Self {
+        env.get_string(&java)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.to_str().map(|s| s.to_string()).map_err(|e| e.to_string()))
+            .jexcept(env, JNI_EXCEPTION_CLASS)
+    }
+}
+
+impl<'local> IntoJava<'local, jni::sys::jstring> for String {
+    fn into_java(self, env: &mut JNIEnv<'local>) -> jni::sys::jstring {
+        match env.new_string(self) {
+            Ok(s) => s.as_raw(),
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+                std::ptr::null_mut()
+            }
+        }
+    }
+}
+
+/// A type that can be marshalled into a single element of a Java `java.util.ArrayList`.
+///
+/// This is the element-side companion to [`IntoJava`]: it knows the Java class it turns itself
+/// into, so a blanket `impl IntoJava<jobject> for Vec<T>` can build a real `ArrayList<T>` for any
+/// `T: JavaArrayElement` instead of every call site hand-rolling its own list-building loop.
+pub trait JavaArrayElement {
+    /// JNI-style class descriptor of the Java type this element becomes, e.g.
+    /// `"co/huggingface/tokenizers/Token"`.
+    const CLASS: &'static str;
+
+    fn into_java_object<'local>(self, env: &mut JNIEnv<'local>) -> JObject<'local>;
+}
+
+impl JavaArrayElement for String {
+    const CLASS: &'static str = "java/lang/String";
+
+    fn into_java_object<'local>(self, env: &mut JNIEnv<'local>) -> JObject<'local> {
+        match env.new_string(self) {
+            Ok(s) => s.into(),
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+                JObject::null()
+            }
+        }
+    }
+}
+
+impl JavaArrayElement for Token {
+    const CLASS: &'static str = "co/huggingface/tokenizers/Token";
+
+    fn into_java_object<'local>(self, env: &mut JNIEnv<'local>) -> JObject<'local> {
+        let value = match env.new_string(&self.value) {
+            Ok(s) => s,
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+                return JObject::null();
+            }
+        };
+        let offsets = match env.new_int_array(2) {
+            Ok(a) => a,
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+                return JObject::null();
+            }
+        };
+        let bounds = [self.offsets.0 as jint, self.offsets.1 as jint];
+        if let Err(e) = env.set_int_array_region(&offsets, 0, &bounds) {
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+            return JObject::null();
+        }
+
+        match env.new_object(
+            Self::CLASS,
+            "(ILjava/lang/String;[I)V",
+            &[
+                JValue::Int(self.id as jint),
+                JValue::Object(&value),
+                JValue::Object(&offsets),
+            ],
+        ) {
+            Ok(obj) => obj,
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+                JObject::null()
+            }
+        }
+    }
+}
+
+/// Reads a Java `java.util.List<String>` into a `Vec<String>`.
+///
+/// This is the `FromJava` counterpart to the `JavaArrayElement`-driven `IntoJava` above, but
+/// takes the list by reference rather than by value since callers (e.g. `decode`) only ever
+/// borrow the Java-side argument.
+pub fn java_string_list_to_vec<'local>(
+    env: &mut JNIEnv<'local>,
+    list: &JObject<'local>,
+) -> Result<Vec<String>, String> {
+    let size = env
+        .call_method(list, "size", "()I", &[])
+        .and_then(|v| v.i())
+        .map_err(|e| e.to_string())?;
+
+    (0..size)
+        .map(|i| {
+            let element = env
+                .call_method(list, "get", "(I)Ljava/lang/Object;", &[JValue::Int(i)])
+                .and_then(|v| v.l())
+                .map_err(|e| e.to_string())?;
+            env.get_string(&JString::from(element))
+                .map_err(|e| e.to_string())
+                .and_then(|s| s.to_str().map(|s| s.to_string()).map_err(|e| e.to_string()))
+        })
+        .collect()
+}
+
+impl<'local, T: JavaArrayElement> IntoJava<'local, jobject> for Vec<T> {
+    fn into_java(self, env: &mut JNIEnv<'local>) -> jobject {
+        let list = match env.new_object("java/util/ArrayList", "(I)V", &[JValue::Int(self.len() as jint)]) {
+            Ok(list) => list,
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, e.to_string());
+                return JObject::null().as_raw();
+            }
+        };
+
+        for item in self {
+            let element = item.into_java_object(env);
+            let _ = env.call_method(&list, "add", "(Ljava/lang/Object;)Z", &[JValue::Object(&element)]);
+        }
+
+        list.as_raw()
+    }
+}
+
+/// A `Result` return throws `JNIException` with the error's `{:?}` message on `Err` and
+/// returns `null`.
+impl<'local, T, E> IntoJava<'local, jobject> for Result<T, E>
+where
+    T: IntoJava<'local, jobject>,
+    E: std::fmt::Debug,
+{
+    fn into_java(self, env: &mut JNIEnv<'local>) -> jobject {
+        match self {
+            Ok(value) => value.into_java(env),
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("{e:?}"));
+                std::ptr::null_mut()
+            }
+        }
+    }
+}
+
+/// Builds a Java `class[]` array from `items`, converting each element via `to_java`.
+///
+/// Unlike the `IntoJava` impl above (which builds an `ArrayList<T>` for `JavaArrayElement`
+/// types), this returns a plain Java object array - what the generated `Encoding`'s fixed-shape
+/// constructor expects for its `String[]`/`Offset[]`/`Integer[]`/`Encoding[]` fields (its `int[]`
+/// fields are built separately as primitive arrays).
+pub fn rust_vec_to_java<'local, T>(
+    env: &mut JNIEnv<'local>,
+    items: &[T],
+    class: &str,
+    mut to_java: impl FnMut(&mut JNIEnv<'local>, &T) -> JObject<'local>,
+) -> JObject<'local> {
+    let element_class = match env.find_class(class) {
+        Ok(cls) => cls,
+        Err(e) => {
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to find class {class}: {e:?}"));
+            return JObject::null();
+        }
+    };
+
+    let array = match env.new_object_array(items.len() as i32, &element_class, JObject::null()) {
+        Ok(arr) => arr,
+        Err(e) => {
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to create {class} array: {e:?}"));
+            return JObject::null();
+        }
+    };
+
+    for (i, item) in items.iter().enumerate() {
+        let element = to_java(env, item);
+        let _ = env.set_object_array_element(&array, i as i32, &element);
+    }
+
+    array.into()
+}
+
+/// Reads a Java `String[]` into a `Vec<String>`.
+///
+/// This is the argument-side counterpart to [`rust_vec_to_java`], used by the batch encoding
+/// entry point to turn its `String[]` input into the `Vec<String>` it passes to `encode_batch`.
+pub fn java_string_array_to_vec<'local>(
+    env: &mut JNIEnv<'local>,
+    array: &JObjectArray<'local>,
+) -> Result<Vec<String>, String> {
+    let len = env.get_array_length(array).map_err(|e| e.to_string())?;
+
+    (0..len)
+        .map(|i| {
+            let element = env.get_object_array_element(array, i).map_err(|e| e.to_string())?;
+            env.get_string(&JString::from(element))
+                .map_err(|e| e.to_string())
+                .and_then(|s| s.to_str().map(|s| s.to_string()).map_err(|e| e.to_string()))
+        })
+        .collect()
+}
+
+/// Boxes an `Option<u32>` as a Java `Integer`, preserving `null` for `None` (used for word ids,
+/// where a token may not correspond to any source word).
+pub fn boxed_word_id<'local>(env: &mut JNIEnv<'local>, id: &Option<u32>) -> JObject<'local> {
+    let Some(id) = id else {
+        return JObject::null();
+    };
+    match env.new_object("java/lang/Integer", "(I)V", &[JValue::Int(*id as jint)]) {
+        Ok(obj) => obj,
+        Err(e) => {
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to box word id: {e:?}"));
+            JObject::null()
+        }
+    }
+}
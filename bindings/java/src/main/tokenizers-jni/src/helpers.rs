@@ -1,15 +1,31 @@
 use jni::JNIEnv;
-use jni::objects::JValue;
-use jni::sys::{jint, jobject};
 
+/// Generic JNI-mechanical failures (string/array marshalling, object allocation, field access)
+/// that aren't specific to any one native call.
+pub const JNI_EXCEPTION_CLASS: &str = "co/huggingface/tokenizers/exceptions/JNIException";
 
+/// Converts a `Result` into its `Ok` value for use at a JNI boundary.
+///
+/// On `Err`, this throws `class` as a pending Java exception (formatting the error via `{:?}`)
+/// and returns a safe default value instead of unwinding, so a single malformed argument or
+/// failed load/encode/decode raises a catchable, specifically-typed Java exception instead of
+/// aborting the JVM.
+pub trait JExceptable<T> {
+    fn jexcept(self, env: &mut JNIEnv, class: &str) -> T;
+}
 
-
-pub fn string_vector_to_arraylist(_env: &mut JNIEnv, vector: &Vec<String>) -> Result<jobject, String>{
-    match _env.new_object("java/util/ArrayList", "(I)V", &[JValue::Int(vector.len() as jint)]){
-        Ok(jarray_) => {
-            return Ok(jarray_.as_raw());
-        },
-        Err(_e) => return Err("Unable to allocate java.util.ArrayList".to_string())
-    };
-}
\ No newline at end of file
+impl<T, E> JExceptable<T> for Result<T, E>
+where
+    T: Default,
+    E: std::fmt::Debug,
+{
+    fn jexcept(self, env: &mut JNIEnv, class: &str) -> T {
+        match self {
+            Ok(value) => value,
+            Err(e) => {
+                let _ = env.throw_new(class, format!("{e:?}"));
+                T::default()
+            }
+        }
+    }
+}
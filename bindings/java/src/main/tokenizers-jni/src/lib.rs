@@ -1,3 +1,5 @@
+mod byte_level;
+mod convert;
 mod glue;
 mod helpers;
 
@@ -5,20 +7,26 @@ extern crate jni;
 extern crate tokenizers;
 
 use jni::{JNIEnv};
-use jni::objects::{JClass, JObject, JValue, JString};
-use jni::sys::{jint, jlong, jobject, jstring};
+use jni::objects::{JClass, JObject, JObjectArray, JValue, JString};
+use jni::sys::{jboolean, jint, jlong, jobject, jstring};
 
 use tokenizers::models::bpe::BPE;
 use tokenizers::tokenizer::Token;
 use tokenizers::pre_tokenizers::whitespace::Whitespace;
 use tokenizers::pre_tokenizers::byte_level::ByteLevel;
 
-use helpers::string_vector_to_arraylist;
+use convert::{boxed_word_id, java_string_array_to_vec, rust_vec_to_java, IntoJava};
+use helpers::{JExceptable, JNI_EXCEPTION_CLASS};
 use glue::reinterpret_cast;
 
 // Constants
 const NATIVE_ALLOCATION_FAILED_EXCEPTION: &str = "co/huggingface/tokenizers/exceptions/NativeAllocationFailedException";
 const STRING_DECODING_EXCEPTION: &str = "co/huggingface/tokenizers/exceptions/StringDecodingException";
+const TOKENIZER_LOAD_EXCEPTION: &str = "co/huggingface/tokenizers/exceptions/TokenizerLoadException";
+const ENCODING_EXCEPTION: &str = "co/huggingface/tokenizers/exceptions/EncodingException";
+const ENCODING_CLASS: &str = "co/huggingface/tokenizers/Encoding";
+const OFFSET_CLASS: &str = "co/huggingface/tokenizers/Offset";
+const ENCODING_CTOR_SIG: &str = "([I[Ljava/lang/String;[Lco/huggingface/tokenizers/Offset;[I[I[I[Ljava/lang/Integer;[Lco/huggingface/tokenizers/Encoding;)V";
 
 
 // Pretokenizer
@@ -34,45 +42,34 @@ pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_White
     match _env.get_field(&_obj, "handle", "J") {
         Ok(ptr) => {
             let _ = _env.set_field(&_obj, "handle", "J", JValue::Long(-1));
-            let _boxed = Box::from_raw(ptr.j().unwrap() as *mut Whitespace);
+            let handle = ptr.j().jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+            let _boxed = Box::from_raw(handle as *mut Whitespace);
         },
         Err(_) => { let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "Unable to retrieve Whitespace ptr"); }
     };
 }
 
-#[no_mangle]
-pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_WhitespacePretokenizer_pretokenize(mut _env: JNIEnv, _obj: JObject, s: JString) -> jobject {
-    // Retrieve Whitespace instance ptr and reinterpret_cast<Whitespace>
-    let _whitespace = match _env.get_field(&_obj, "handle", "J"){
-        Ok(ptr) => match ptr.j(){
-            Ok(ptr) => Some(&mut *(ptr as *mut Whitespace)),
-            Err(_) => {
-                let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "Failed to reinterpret Whitespace ptr");
-                None
-            }
-        },
-        Err(_) => {
-            let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "Failed to retrieve Whitespace ptr");
-            None
-        }
-    };
-
-    // Simple implementation - just return the input as a single token
-    let input_str = _env.get_string(&s).unwrap().to_str().unwrap().to_string();
-    let tokens = vec![input_str];
-    match string_vector_to_arraylist(&mut _env, &tokens){
-        Ok(jarray_tokens) => return jarray_tokens,
-        _ => {
-            let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "");
-            return JObject::null().as_raw();
-        }
-    }
+// Generated by `#[jni]` below: handle deref, argument/return conversion and exception mapping
+// are all handled by the macro, so this is just the pretokenization logic itself.
+#[tokenizers_jni_macro::jni(package = "co.huggingface.tokenizers.pretokenizers", class = "WhitespacePretokenizer", ptr)]
+fn pretokenize(_whitespace: &mut Whitespace, s: String) -> Vec<Token> {
+    // Simple implementation - just return the input as a single token. The id is a placeholder:
+    // a pretokenizer has no vocabulary to resolve it against.
+    let len = s.len();
+    vec![Token::new(0, s, (0, len))]
 }
 
 //// Byte Level
 #[no_mangle]
-pub extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteLevelPretokenizer_allocate(_env: JNIEnv, _class: JClass, _obj: JObject) -> jlong {
-    return Box::into_raw(Box::new(ByteLevel::default())) as jlong;
+pub extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteLevelPretokenizer_allocate(
+    _env: JNIEnv,
+    _class: JClass,
+    _obj: JObject,
+    add_prefix_space: jboolean,
+    trim_offsets: jboolean,
+) -> jlong {
+    let byte_level = ByteLevel::new(add_prefix_space != 0, trim_offsets != 0, true);
+    return Box::into_raw(Box::new(byte_level)) as jlong;
 }
 
 #[no_mangle]
@@ -81,7 +78,7 @@ pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteL
     match _env.get_field(&_obj, "handle", "J") {
         Ok(ptr) => {
             let _ = _env.set_field(&_obj, "handle", "J", JValue::Long(-1));
-            let pretokenizer = reinterpret_cast::<ByteLevel>(ptr.j().unwrap());
+            let pretokenizer = reinterpret_cast::<ByteLevel>(ptr.j().jexcept(&mut _env, JNI_EXCEPTION_CLASS));
             let _boxed = Box::from_raw(pretokenizer);
         },
         Err(_) => { let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "Unable to retrieve ByteLevel ptr"); }
@@ -90,46 +87,104 @@ pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteL
 
 #[no_mangle]
 pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteLevelPretokenizer_pretokenize(mut _env: JNIEnv, _obj: JObject, s: JString) -> jobject {
-    // Retrieve Whitespace instance ptr and reinterpret_cast<Whitespace>
-    let _pretokenizer = reinterpret_cast::<ByteLevel>(_env.get_field(&_obj, "handle", "J").unwrap().j().unwrap());
-
-    // Simple implementation - just return the input as a single token
-    let input_str = _env.get_string(&s).unwrap().to_str().unwrap().to_string();
-    let tokens = vec![input_str];
-    match string_vector_to_arraylist(&mut _env, &tokens){
-        Ok(jarray_tokens) => return jarray_tokens,
-        _ => {
-            let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "");
-            return JObject::null().as_raw();
-        }
-    }
+    // Retrieve ByteLevel instance ptr and reinterpret_cast<ByteLevel>
+    let pretokenizer = reinterpret_cast::<ByteLevel>(
+        _env.get_field(&_obj, "handle", "J").and_then(|v| v.j()).jexcept(&mut _env, JNI_EXCEPTION_CLASS)
+    );
+
+    let input_str: String = _env
+        .get_string(&s)
+        .map_err(|e| e.to_string())
+        .and_then(|s| s.to_str().map(|s| s.to_string()).map_err(|e| e.to_string()))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let prefixed = if pretokenizer.add_prefix_space && !input_str.starts_with(' ') {
+        format!(" {input_str}")
+    } else {
+        input_str
+    };
+
+    let byte_to_char = byte_level::byte_to_char();
+    let tokens: Vec<Token> = split_on_whitespace_boundaries(&prefixed)
+        .into_iter()
+        .map(|(word, start, end)| {
+            // The byte-level mapping turns the word's leading space into its own printable
+            // character, so the *value* always reflects the full word; `trim_offsets` only
+            // controls whether the reported span includes that leading/trailing whitespace.
+            let value = byte_level::encode_bytes(word.as_bytes(), &byte_to_char);
+            let (start, end) = if pretokenizer.trim_offsets {
+                let trimmed_start = start + (word.len() - word.trim_start().len());
+                let trimmed_end = start + word.trim_end().len();
+                (trimmed_start, trimmed_end.max(trimmed_start))
+            } else {
+                (start, end)
+            };
+            Token::new(0, value, (start, end))
+        })
+        .collect();
+
+    tokens.into_java(&mut _env)
 }
 
 #[no_mangle]
-pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteLevelPretokenizer_decode(mut _env: JNIEnv, _obj: JObject, _words: JObject) -> jstring {
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_pretokenizers_ByteLevelPretokenizer_decode(mut _env: JNIEnv, _obj: JObject, words: JObject) -> jstring {
     // Retrieve ByteLevel instance ptr and reinterpret_cast<ByteLevel>
-    let _pretokenizer = reinterpret_cast::<ByteLevel>(_env.get_field(&_obj, "handle", "J").unwrap().j().unwrap());
+    let _pretokenizer = reinterpret_cast::<ByteLevel>(
+        _env.get_field(&_obj, "handle", "J").and_then(|v| v.j()).jexcept(&mut _env, JNI_EXCEPTION_CLASS)
+    );
 
-    // Simple implementation - just return empty string for now
-    match _env.new_string(""){
-        Ok(jstr) => return jstr.as_raw(),
-        _ => {
-            let _ = _env.throw_new(STRING_DECODING_EXCEPTION, "");
-            return JObject::null().as_raw();
+    let words: Vec<String> = convert::java_string_list_to_vec(&mut _env, &words).jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+    let concatenated: String = words.concat();
+
+    let char_to_byte = byte_level::char_to_byte();
+    let decoded = byte_level::decode_chars(&concatenated, &char_to_byte)
+        .and_then(|bytes| String::from_utf8(bytes).map_err(|e| e.to_string()));
+
+    match decoded {
+        Ok(s) => match _env.new_string(s) {
+            Ok(jstr) => jstr.as_raw(),
+            Err(_) => {
+                let _ = _env.throw_new(STRING_DECODING_EXCEPTION, "Unable to allocate decoded string");
+                JObject::null().as_raw()
+            }
+        },
+        Err(e) => {
+            let _ = _env.throw_new(STRING_DECODING_EXCEPTION, e);
+            JObject::null().as_raw()
+        }
+    }
+}
+
+/// Splits `s` on Unicode whitespace (`char::is_whitespace`), keeping the separators attached to the following word and
+/// tracking each word's byte offsets in `s`.
+fn split_on_whitespace_boundaries(s: &str) -> Vec<(&str, usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c.is_whitespace() && i != start {
+            words.push((&s[start..i], start, i));
+            start = i;
         }
     }
+    if start < s.len() {
+        words.push((&s[start..], start, s.len()));
+    }
+    words
 }
 
 // BPE
 #[no_mangle]
 pub extern "system" fn Java_co_huggingface_tokenizers_models_BytePairEncoder_fromFiles(mut _env: JNIEnv, _class: JClass, vocabs: JString, merges: JString) -> jobject {
-    let vocabs: String = _env.get_string(&vocabs)
-        .expect("Couldn't get vocab file path")
-        .into();
-
-    let merges: String = _env.get_string(&merges)
-        .expect("Couldn't get merges file path")
-        .into();
+    let vocabs: String = _env
+        .get_string(&vocabs)
+        .map(|s| s.to_str().map(|s| s.to_string()).unwrap_or_default())
+        .map_err(|e| e.to_string())
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+    let merges: String = _env
+        .get_string(&merges)
+        .map(|s| s.to_str().map(|s| s.to_string()).unwrap_or_default())
+        .map_err(|e| e.to_string())
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
 
     let bpe: Result<Box<BPE>, String> = match tokenizers::models::bpe::BPE::from_file(&vocabs, &merges).build() {
         Ok(bpe) => Ok(Box::new(bpe)),
@@ -157,18 +212,459 @@ pub extern "system" fn Java_co_huggingface_tokenizers_models_BytePairEncoder_fro
 #[no_mangle]
 pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_BytePairEncoder_tokenize(mut _env: JNIEnv, _obj: JObject, _words: JObject) -> jobject {
     // Retrieve BytePairEncoder object
-    let _bpe = reinterpret_cast::<BPE>(_env.get_field(&_obj, "handle", "J").unwrap().j().unwrap());
+    let _bpe = reinterpret_cast::<BPE>(
+        _env.get_field(&_obj, "handle", "J").and_then(|v| v.j()).jexcept(&mut _env, JNI_EXCEPTION_CLASS)
+    );
 
     // Simple implementation - just return empty token list for now
     let tokens: Vec<Token> = Vec::new();
+    tokens.into_java(&mut _env)
+}
+
+// Full tokenizer
+//
+// `BytePairEncoder_fromFiles` above only builds a bare model from a legacy vocab/merges pair,
+// with no normalizer, pretokenizer or post-processor attached. `models/Tokenizer` instead loads
+// a complete, modern `tokenizer.json` through the full normalize -> pretokenize -> model ->
+// postprocess pipeline, so Java callers don't have to wire those stages together by hand.
+
+/// Reads the `handle` field off a Java `Tokenizer`, failing if the field can't be read or the
+/// tokenizer has already been closed (its handle zeroed by `free`/`close`), so every native
+/// method guards against dereferencing a dangling pointer instead of needing its own check.
+fn get_live_handle(env: &mut JNIEnv, obj: &JObject) -> Result<jlong, String> {
+    let handle = env
+        .get_field(obj, "handle", "J")
+        .and_then(|v| v.j())
+        .map_err(|e| format!("Failed to get tokenizer handle: {e:?}"))?;
+    if handle == 0 {
+        return Err("Tokenizer has already been closed".to_string());
+    }
+    Ok(handle)
+}
 
-    match _env.new_object("java/util/ArrayList", "(I)V", &[JValue::Int(tokens.len() as jint)]) {
-        Ok(jarray_) => {
-            return jarray_.as_raw()
+/// Copies a `&[u32]` into a freshly allocated Java `int[]`, throwing `JNIException` and
+/// returning `None` if allocation fails.
+fn u32_slice_to_int_array<'local>(env: &mut JNIEnv<'local>, values: &[u32]) -> Option<jni::objects::JIntArray<'local>> {
+    let array = match env.new_int_array(values.len() as i32) {
+        Ok(arr) => arr,
+        Err(e) => {
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to create int array: {e:?}"));
+            return None;
+        }
+    };
+    let values_i32: Vec<i32> = values.iter().map(|&x| x as jint).collect();
+    let _ = env.set_int_array_region(&array, 0, &values_i32);
+    Some(array)
+}
+
+/// Builds a full Java `Encoding` object from a Rust `Encoding`, including its overflowing
+/// encodings (recursively built the same way). Returns `JObject::null()` (having already thrown)
+/// on any marshalling failure.
+fn build_encoding_object<'local>(env: &mut JNIEnv<'local>, encoding: &tokenizers::tokenizer::Encoding) -> JObject<'local> {
+    let Some(ids_array) = u32_slice_to_int_array(env, encoding.get_ids()) else {
+        return JObject::null();
+    };
+    let Some(attention_mask_array) = u32_slice_to_int_array(env, encoding.get_attention_mask()) else {
+        return JObject::null();
+    };
+    let Some(type_ids_array) = u32_slice_to_int_array(env, encoding.get_type_ids()) else {
+        return JObject::null();
+    };
+    let Some(special_tokens_mask_array) = u32_slice_to_int_array(env, encoding.get_special_tokens_mask()) else {
+        return JObject::null();
+    };
+
+    let tokens_array = rust_vec_to_java(env, encoding.get_tokens(), "java/lang/String", |env, token| {
+        match env.new_string(token) {
+            Ok(s) => s.into(),
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to create token string: {e:?}"));
+                JObject::null()
+            }
+        }
+    });
+
+    let offsets_array = rust_vec_to_java(env, encoding.get_offsets(), OFFSET_CLASS, |env, offset| {
+        match env.new_object(OFFSET_CLASS, "(II)V", &[
+            JValue::Int(offset.0 as jint),
+            JValue::Int(offset.1 as jint),
+        ]) {
+            Ok(obj) => obj,
+            Err(e) => {
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to create Offset object: {e:?}"));
+                JObject::null()
+            }
+        }
+    });
+
+    let word_ids_array = rust_vec_to_java(env, encoding.get_word_ids(), "java/lang/Integer", boxed_word_id);
+
+    let overflowing_array = rust_vec_to_java(env, encoding.get_overflowing(), ENCODING_CLASS, |env, overflowing| {
+        build_encoding_object(env, overflowing)
+    });
+
+    match env.new_object(ENCODING_CLASS, ENCODING_CTOR_SIG, &[
+        JValue::Object(&JObject::from(ids_array)),
+        JValue::Object(&tokens_array),
+        JValue::Object(&offsets_array),
+        JValue::Object(&JObject::from(attention_mask_array)),
+        JValue::Object(&JObject::from(type_ids_array)),
+        JValue::Object(&JObject::from(special_tokens_mask_array)),
+        JValue::Object(&word_ids_array),
+        JValue::Object(&overflowing_array),
+    ]) {
+        Ok(obj) => obj,
+        Err(e) => {
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to create Encoding object: {e:?}"));
+            JObject::null()
+        }
+    }
+}
+
+/// Boxes a loaded `Tokenizer`, stashes it as the `handle` field of a freshly allocated Java
+/// `models.Tokenizer`, and returns that object. Shared by every constructor (`fromFile`,
+/// `fromString`, `fromBytes`) so the handle bookkeeping (and its allocation-failure cleanup) is
+/// written once.
+fn wrap_tokenizer(env: &mut JNIEnv, tokenizer: tokenizers::Tokenizer) -> jobject {
+    let handle = Box::into_raw(Box::new(tokenizer)) as jlong;
+
+    match env.new_object("co/huggingface/tokenizers/models/Tokenizer", "()V", &[]) {
+        Ok(j_tokenizer) => match env.set_field(&j_tokenizer, "handle", "J", JValue::Long(handle)) {
+            Ok(()) => j_tokenizer.as_raw(),
+            Err(e) => {
+                unsafe {
+                    let _ = Box::from_raw(handle as *mut tokenizers::Tokenizer);
+                }
+                let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Unable to set Tokenizer.handle: {e:?}"));
+                JObject::null().as_raw()
+            }
         },
-        Err(_) => {
-            let _ = _env.throw_new(NATIVE_ALLOCATION_FAILED_EXCEPTION, "Failed to allocate ArrayList<Token>");
-            return JObject::null().as_raw()
+        Err(e) => {
+            unsafe {
+                let _ = Box::from_raw(handle as *mut tokenizers::Tokenizer);
+            }
+            let _ = env.throw_new(JNI_EXCEPTION_CLASS, format!("Unable to create Tokenizer object: {e:?}"));
+            JObject::null().as_raw()
         }
     }
-}
\ No newline at end of file
+}
+
+#[no_mangle]
+pub extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_fromFile(mut _env: JNIEnv, _class: JClass, file_path: JString) -> jobject {
+    let file_path: String = _env
+        .get_string(&file_path)
+        .map(|s| s.into())
+        .map_err(|e| format!("Couldn't get file path: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let tokenizer: tokenizers::Tokenizer = match tokenizers::Tokenizer::from_file(&file_path) {
+        Ok(tokenizer) => tokenizer,
+        Err(e) => {
+            let _ = _env.throw_new(TOKENIZER_LOAD_EXCEPTION, format!("Failed to load tokenizer from file: {e:?}"));
+            return JObject::null().as_raw();
+        }
+    };
+
+    wrap_tokenizer(&mut _env, tokenizer)
+}
+
+/// Loads a tokenizer from an in-memory JSON string, for callers that already have the tokenizer
+/// JSON (e.g. read from a classpath resource) instead of a file path.
+#[no_mangle]
+pub extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_fromString(mut _env: JNIEnv, _class: JClass, json: JString) -> jobject {
+    let json: String = _env
+        .get_string(&json)
+        .map(|s| s.into())
+        .map_err(|e| format!("Couldn't get tokenizer JSON string: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let tokenizer: tokenizers::Tokenizer = match json.parse() {
+        Ok(tokenizer) => tokenizer,
+        Err(e) => {
+            let _ = _env.throw_new(TOKENIZER_LOAD_EXCEPTION, format!("Failed to parse tokenizer JSON: {e:?}"));
+            return JObject::null().as_raw();
+        }
+    };
+
+    wrap_tokenizer(&mut _env, tokenizer)
+}
+
+/// Loads a tokenizer from a raw byte buffer (the UTF-8 encoded tokenizer JSON), for callers
+/// fetching it over the network rather than writing a temp file first.
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_fromBytes(
+    mut _env: JNIEnv,
+    _class: JClass,
+    buf: jni::sys::jbyteArray,
+) -> jobject {
+    let buf = jni::objects::JByteArray::from_raw(buf);
+    let len = _env
+        .get_array_length(&buf)
+        .map_err(|e| format!("Failed to get byte buffer length: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let mut bytes = vec![0i8; len as usize];
+    if let Err(e) = _env.get_byte_array_region(&buf, 0, &mut bytes) {
+        let _ = _env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to read byte buffer: {e:?}"));
+        return JObject::null().as_raw();
+    }
+    let bytes: Vec<u8> = bytes.into_iter().map(|b| b as u8).collect();
+
+    let json = String::from_utf8(bytes)
+        .map_err(|e| format!("Tokenizer bytes are not valid UTF-8: {e:?}"))
+        .jexcept(&mut _env, TOKENIZER_LOAD_EXCEPTION);
+
+    let tokenizer: tokenizers::Tokenizer = match json.parse() {
+        Ok(tokenizer) => tokenizer,
+        Err(e) => {
+            let _ = _env.throw_new(TOKENIZER_LOAD_EXCEPTION, format!("Failed to parse tokenizer JSON: {e:?}"));
+            return JObject::null().as_raw();
+        }
+    };
+
+    wrap_tokenizer(&mut _env, tokenizer)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_encode(
+    mut _env: JNIEnv,
+    _obj: JObject,
+    text: JString,
+    add_special_tokens: jboolean,
+) -> jobject {
+    let handle = match get_live_handle(&mut _env, &_obj) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = _env.throw_new(JNI_EXCEPTION_CLASS, e);
+            return JObject::null().as_raw();
+        }
+    };
+    let tokenizer = &*(handle as *mut tokenizers::Tokenizer);
+
+    let input_text: String = _env
+        .get_string(&text)
+        .map(|s| s.into())
+        .map_err(|e| format!("Failed to get input text: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let encoding = tokenizer
+        .encode(input_text.as_str(), add_special_tokens != 0)
+        .map_err(|e| format!("Failed to encode text: {e:?}"))
+        .jexcept(&mut _env, ENCODING_EXCEPTION);
+
+    build_encoding_object(&mut _env, &encoding).as_raw()
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_encodeBatch(
+    mut _env: JNIEnv,
+    _obj: JObject,
+    texts: JObjectArray,
+    add_special_tokens: jboolean,
+) -> jobject {
+    let handle = match get_live_handle(&mut _env, &_obj) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = _env.throw_new(JNI_EXCEPTION_CLASS, e);
+            return JObject::null().as_raw();
+        }
+    };
+    let tokenizer = &*(handle as *mut tokenizers::Tokenizer);
+
+    let inputs: Vec<String> = java_string_array_to_vec(&mut _env, &texts)
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let encodings = tokenizer
+        .encode_batch(inputs, add_special_tokens != 0)
+        .map_err(|e| format!("Failed to encode batch: {e:?}"))
+        .jexcept(&mut _env, ENCODING_EXCEPTION);
+
+    rust_vec_to_java(&mut _env, &encodings, ENCODING_CLASS, |env, encoding| {
+        build_encoding_object(env, encoding)
+    })
+    .as_raw()
+}
+
+// `models.Tokenizer.decode`: generated by `#[tokenizer_jni]` instead of hand-written, since this
+// is exactly the shape the macro covers (a `&Tokenizer` receiver, a single `Vec<u32>` argument, a
+// `Result<String, _>` return mapped to one exception class).
+#[tokenizers_jni_macro::tokenizer_jni(
+    package = "co.huggingface.tokenizers.models",
+    class = "Tokenizer",
+    err_class = "co/huggingface/tokenizers/exceptions/DecodingException"
+)]
+fn decode(tokenizer: &tokenizers::Tokenizer, ids: Vec<u32>) -> Result<String, tokenizers::tokenizer::Error> {
+    tokenizer.decode(&ids, false)
+}
+
+fn parse_truncation_direction(s: &str) -> Option<tokenizers::tokenizer::TruncationDirection> {
+    match s {
+        "LEFT" => Some(tokenizers::tokenizer::TruncationDirection::Left),
+        "RIGHT" => Some(tokenizers::tokenizer::TruncationDirection::Right),
+        _ => None,
+    }
+}
+
+fn parse_truncation_strategy(s: &str) -> Option<tokenizers::tokenizer::TruncationStrategy> {
+    match s {
+        "LONGEST_FIRST" => Some(tokenizers::tokenizer::TruncationStrategy::LongestFirst),
+        "ONLY_FIRST" => Some(tokenizers::tokenizer::TruncationStrategy::OnlyFirst),
+        "ONLY_SECOND" => Some(tokenizers::tokenizer::TruncationStrategy::OnlySecond),
+        _ => None,
+    }
+}
+
+fn parse_padding_direction(s: &str) -> Option<tokenizers::tokenizer::PaddingDirection> {
+    match s {
+        "LEFT" => Some(tokenizers::tokenizer::PaddingDirection::Left),
+        "RIGHT" => Some(tokenizers::tokenizer::PaddingDirection::Right),
+        _ => None,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_enableTruncation(
+    mut _env: JNIEnv,
+    _obj: JObject,
+    max_length: jint,
+    stride: jint,
+    strategy: JString,
+    direction: JString,
+) {
+    let handle = match get_live_handle(&mut _env, &_obj) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = _env.throw_new(JNI_EXCEPTION_CLASS, e);
+            return;
+        }
+    };
+    let tokenizer = &mut *(handle as *mut tokenizers::Tokenizer);
+
+    let strategy: String = _env
+        .get_string(&strategy)
+        .map(|s| s.into())
+        .map_err(|e| format!("Failed to get truncation strategy: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+    let direction: String = _env
+        .get_string(&direction)
+        .map(|s| s.into())
+        .map_err(|e| format!("Failed to get truncation direction: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let Some(strategy) = parse_truncation_strategy(&strategy) else {
+        let _ = _env.throw_new(JNI_EXCEPTION_CLASS, format!("Unknown truncation strategy: {strategy}"));
+        return;
+    };
+    let Some(direction) = parse_truncation_direction(&direction) else {
+        let _ = _env.throw_new(JNI_EXCEPTION_CLASS, format!("Unknown truncation direction: {direction}"));
+        return;
+    };
+
+    let params = tokenizers::tokenizer::TruncationParams {
+        max_length: max_length as usize,
+        stride: stride as usize,
+        strategy,
+        direction,
+    };
+    if let Err(e) = tokenizer.with_truncation(Some(params)) {
+        let _ = _env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to enable truncation: {e:?}"));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_disableTruncation(mut _env: JNIEnv, _obj: JObject) {
+    let handle = match get_live_handle(&mut _env, &_obj) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = _env.throw_new(JNI_EXCEPTION_CLASS, e);
+            return;
+        }
+    };
+    let tokenizer = &mut *(handle as *mut tokenizers::Tokenizer);
+    if let Err(e) = tokenizer.with_truncation(None) {
+        let _ = _env.throw_new(JNI_EXCEPTION_CLASS, format!("Failed to disable truncation: {e:?}"));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_enablePadding(
+    mut _env: JNIEnv,
+    _obj: JObject,
+    fixed_length: jint,
+    direction: JString,
+    pad_id: jint,
+    pad_token: JString,
+    pad_to_multiple_of: jint,
+) {
+    let handle = match get_live_handle(&mut _env, &_obj) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = _env.throw_new(JNI_EXCEPTION_CLASS, e);
+            return;
+        }
+    };
+    let tokenizer = &mut *(handle as *mut tokenizers::Tokenizer);
+
+    let direction: String = _env
+        .get_string(&direction)
+        .map(|s| s.into())
+        .map_err(|e| format!("Failed to get padding direction: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+    let pad_token: String = _env
+        .get_string(&pad_token)
+        .map(|s| s.into())
+        .map_err(|e| format!("Failed to get pad token: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+
+    let Some(direction) = parse_padding_direction(&direction) else {
+        let _ = _env.throw_new(JNI_EXCEPTION_CLASS, format!("Unknown padding direction: {direction}"));
+        return;
+    };
+
+    // `fixedLength < 0` means "pad every batch to its own longest sequence"; a non-negative value
+    // pins every sequence to that length, matching `PaddingStrategy::BatchLongest`/`Fixed(_)`.
+    let strategy = if fixed_length < 0 {
+        tokenizers::tokenizer::PaddingStrategy::BatchLongest
+    } else {
+        tokenizers::tokenizer::PaddingStrategy::Fixed(fixed_length as usize)
+    };
+
+    tokenizer.with_padding(Some(tokenizers::tokenizer::PaddingParams {
+        strategy,
+        direction,
+        pad_to_multiple_of: if pad_to_multiple_of > 0 { Some(pad_to_multiple_of as usize) } else { None },
+        pad_id: pad_id as u32,
+        pad_type_id: 0,
+        pad_token,
+    }));
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_disablePadding(mut _env: JNIEnv, _obj: JObject) {
+    let handle = match get_live_handle(&mut _env, &_obj) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = _env.throw_new(JNI_EXCEPTION_CLASS, e);
+            return;
+        }
+    };
+    let tokenizer = &mut *(handle as *mut tokenizers::Tokenizer);
+    tokenizer.with_padding(None);
+}
+
+// `models.Tokenizer.free`: backs `Tokenizer.close()`. Dropping the boxed tokenizer and zeroing
+// `handle` makes a second close() (or any other native call after close()) a clean no-op/thrown
+// exception via `get_live_handle`, rather than a use-after-free.
+#[no_mangle]
+pub unsafe extern "system" fn Java_co_huggingface_tokenizers_models_Tokenizer_free(mut _env: JNIEnv, _obj: JObject) {
+    let handle = _env
+        .get_field(&_obj, "handle", "J")
+        .and_then(|v| v.j())
+        .map_err(|e| format!("Failed to get tokenizer handle: {e:?}"))
+        .jexcept(&mut _env, JNI_EXCEPTION_CLASS);
+    if handle == 0 {
+        return;
+    }
+    drop(Box::from_raw(handle as *mut tokenizers::Tokenizer));
+    let _ = _env.set_field(&_obj, "handle", "J", JValue::Long(0));
+}
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 use crate::models::PyModel;
@@ -6,8 +7,9 @@ use pyo3::exceptions;
 use pyo3::prelude::*;
 use pyo3::types::*;
 use serde::{Deserialize, Serialize};
-use tk::models::TrainerWrapper;
-use tk::Trainer;
+use tk::models::bpe::TrainingReport;
+use tk::models::{ModelWrapper, TrainerWrapper};
+use tk::{Model, Trainer};
 use tokenizers as tk;
 
 /// Base class for all trainers
@@ -19,12 +21,19 @@ use tokenizers as tk;
 #[serde(transparent)]
 pub struct PyTrainer {
     pub trainer: Arc<RwLock<TrainerWrapper>>,
+    /// An optional Python callable invoked with structured training-progress events. Not part of
+    /// the pickled representation - a live callback can't survive a round-trip anyway.
+    #[serde(skip)]
+    callback: Option<PyObject>,
 }
 
 impl PyTrainer {
     #[cfg(test)]
     pub(crate) fn new(trainer: Arc<RwLock<TrainerWrapper>>) -> Self {
-        PyTrainer { trainer }
+        PyTrainer {
+            trainer,
+            callback: None,
+        }
     }
     pub(crate) fn get_as_subtype(&self, py: Python<'_>) -> PyResult<PyObject> {
         let base = self.clone();
@@ -83,6 +92,71 @@ impl PyTrainer {
         crate::utils::serde_pyo3::to_string(self)
             .map_err(|e| exceptions::PyException::new_err(e.to_string()))
     }
+
+    /// Registers a callable invoked with structured training-progress events during `train`, for
+    /// building custom UIs or streaming metrics without parsing a progress bar. Currently honored
+    /// by :class:`BpeTrainer` and :class:`WordPieceTrainer`; other trainers ignore it.
+    ///
+    /// Args:
+    ///     callback (:obj:`Callable[[dict], None]`, `optional`):
+    ///         Called with a ``dict`` payload for each event (keys depend on ``event``:
+    ///         ``"alphabet_computed"``, ``"counting_complete"``, or ``"merge_progress"``). Pass
+    ///         ``None`` to clear a previously registered callback.
+    fn set_progress_callback(&mut self, callback: Option<PyObject>) {
+        self.callback = callback;
+    }
+
+    /// Trains `model`, same as the regular training flow, but additionally returns a structured
+    /// report: the initial alphabet actually kept (after `limit_alphabet`/`initial_alphabet`),
+    /// the merges applied in order with the frequency that triggered each, and how many candidate
+    /// merges were dropped for exceeding `max_token_length`.
+    ///
+    /// Only :class:`BpeTrainer` and :class:`WordPieceTrainer` populate ``merges`` and
+    /// ``dropped_by_max_token_length``; other trainers still train `model` but return an empty
+    /// report.
+    ///
+    /// Args:
+    ///     model (:class:`~tokenizers.models.Model`):
+    ///         The model to train.
+    ///
+    /// Returns:
+    ///     Tuple[List[AddedToken], dict]: the special tokens that were added, and the report.
+    fn train_with_report(
+        &self,
+        py: Python,
+        model: Py<PyModel>,
+    ) -> PyResult<(Vec<PyAddedToken>, PyObject)> {
+        let model_ref = model.borrow(py);
+        let mut model_guard = model_ref.model.write().unwrap();
+
+        let trainer_guard = self.trainer.read().unwrap();
+        let (added_tokens, report) = if let (TrainerWrapper::BpeTrainer(t), ModelWrapper::BPE(bpe)) =
+            (&*trainer_guard, &mut *model_guard)
+        {
+            t.train_with_report(bpe)
+                .map_err(|e| exceptions::PyException::new_err(e.to_string()))?
+        } else if let (TrainerWrapper::WordPieceTrainer(t), ModelWrapper::WordPiece(wp)) =
+            (&*trainer_guard, &mut *model_guard)
+        {
+            t.train_with_report(wp)
+                .map_err(|e| exceptions::PyException::new_err(e.to_string()))?
+        } else {
+            let added = trainer_guard
+                .train(&mut model_guard)
+                .map_err(|e| exceptions::PyException::new_err(e.to_string()))?;
+            (added, TrainingReport::default())
+        };
+
+        let py_added_tokens: Vec<PyAddedToken> =
+            added_tokens.into_iter().map(|tok| tok.into()).collect();
+
+        let report_dict = PyDict::new(py);
+        report_dict.set_item("alphabet", report.alphabet)?;
+        report_dict.set_item("merges", report.merges)?;
+        report_dict.set_item("dropped_by_max_token_length", report.dropped_by_max_token_length)?;
+
+        Ok((py_added_tokens, report_dict.into()))
+    }
 }
 
 impl Trainer for PyTrainer {
@@ -93,10 +167,62 @@ impl Trainer for PyTrainer {
     }
 
     fn train(&self, model: &mut PyModel) -> tk::Result<Vec<tk::AddedToken>> {
-        self.trainer
+        // Install the callback under a short-lived write lock, released before `train` itself
+        // runs under a read lock, so the Python callable is never invoked while the write guard
+        // is held (it would deadlock if the callback tried to touch the trainer again).
+        if let Some(callback) = &self.callback {
+            let callback = callback.clone();
+            let rust_callback = tk::models::bpe::TrainerCallback::new(move |event| {
+                Python::with_gil(|py| {
+                    let dict = PyDict::new(py);
+                    match event {
+                        tk::models::bpe::TrainerEvent::AlphabetComputed { size } => {
+                            let _ = dict.set_item("event", "alphabet_computed");
+                            let _ = dict.set_item("alphabet_size", size);
+                        }
+                        tk::models::bpe::TrainerEvent::CountingComplete { unique_words } => {
+                            let _ = dict.set_item("event", "counting_complete");
+                            let _ = dict.set_item("unique_words", unique_words);
+                        }
+                        tk::models::bpe::TrainerEvent::MergeProgress {
+                            vocab_size,
+                            merges_applied,
+                            last_pair,
+                            frequency,
+                        } => {
+                            let _ = dict.set_item("event", "merge_progress");
+                            let _ = dict.set_item("vocab_size", vocab_size);
+                            let _ = dict.set_item("merges_applied", merges_applied);
+                            let _ = dict.set_item("last_pair", last_pair);
+                            let _ = dict.set_item("frequency", frequency);
+                        }
+                    }
+                    let _ = callback.call1(py, (dict,));
+                });
+            });
+
+            match &mut *self.trainer.write().unwrap() {
+                TrainerWrapper::BpeTrainer(t) => t.set_callback(Some(rust_callback)),
+                TrainerWrapper::WordPieceTrainer(t) => t.set_callback(Some(rust_callback)),
+                _ => {}
+            }
+        }
+
+        let result = self
+            .trainer
             .read()
             .unwrap()
-            .train(&mut model.model.write().unwrap())
+            .train(&mut model.model.write().unwrap());
+
+        if self.callback.is_some() {
+            match &mut *self.trainer.write().unwrap() {
+                TrainerWrapper::BpeTrainer(t) => t.set_callback(None),
+                TrainerWrapper::WordPieceTrainer(t) => t.set_callback(None),
+                _ => {}
+            }
+        }
+
+        result
     }
 
     fn feed<I, S, F>(&mut self, iterator: I, process: F) -> tk::Result<()>
@@ -116,6 +242,7 @@ where
     fn from(trainer: I) -> Self {
         PyTrainer {
             trainer: Arc::new(RwLock::new(trainer.into())),
+            callback: None,
         }
     }
 }
@@ -146,6 +273,22 @@ macro_rules! setter {
     }};
 }
 
+/// Validates a Python `dict[str, int]` of word counts ahead of
+/// [`BpeTrainer::feed_word_counts`](tk::models::bpe::BpeTrainer::feed_word_counts) and its
+/// siblings, rejecting negative counts before they ever reach the trainer.
+fn validate_word_counts(word_counts: HashMap<String, i64>) -> PyResult<Vec<(String, u64)>> {
+    word_counts
+        .into_iter()
+        .map(|(word, count)| {
+            u64::try_from(count).map(|count| (word, count)).map_err(|_| {
+                exceptions::PyValueError::new_err(format!(
+                    "word_counts values must be non-negative, got {count} for {word:?}"
+                ))
+            })
+        })
+        .collect()
+}
+
 /// Trainer capable of training a BPE model
 ///
 /// Args:
@@ -181,6 +324,15 @@ macro_rules! setter {
 ///         This can help with reducing polluting your vocabulary with
 ///         highly repetitive tokens like `======` for wikipedia
 ///
+///     min_token_length (:obj:`int`, `optional`):
+///         Prevents creating tokens shorter than the specified size, symmetric to
+///         `max_token_length`. Candidate merges that would violate this are skipped, while their
+///         sub-pieces remain free to merge elsewhere.
+///
+///     max_number_length (:obj:`int`, `optional`):
+///         Caps the length of merged tokens made up entirely of digits, independently of
+///         `max_token_length`. Useful when the vocabulary is meant to double as a wordlist.
+///
 #[pyclass(extends=PyTrainer, module = "tokenizers.trainers", name = "BpeTrainer")]
 pub struct PyBpeTrainer {}
 #[pymethods]
@@ -311,6 +463,38 @@ impl PyBpeTrainer {
         setter!(self_, BpeTrainer, end_of_word_suffix, suffix);
     }
 
+    /// Injects already-counted word frequencies directly into the trainer, bypassing
+    /// :meth:`~tokenizers.trainers.Trainer.feed` entirely.
+    ///
+    /// Args:
+    ///     word_counts (:obj:`Dict[str, int]`):
+    ///         A mapping of words to their frequency in the corpus. Counts must be non-negative.
+    fn word_counts(self_: PyRef<Self>, word_counts: HashMap<String, i64>) -> PyResult<()> {
+        let counts = validate_word_counts(word_counts)?;
+        setter!(self_, BpeTrainer, @feed_word_counts, counts);
+        Ok(())
+    }
+
+    #[getter]
+    fn get_min_token_length(self_: PyRef<Self>) -> Option<usize> {
+        getter!(self_, BpeTrainer, min_token_length)
+    }
+
+    #[setter]
+    fn set_min_token_length(self_: PyRef<Self>, length: Option<usize>) {
+        setter!(self_, BpeTrainer, min_token_length, length);
+    }
+
+    #[getter]
+    fn get_max_number_length(self_: PyRef<Self>) -> Option<usize> {
+        getter!(self_, BpeTrainer, max_number_length)
+    }
+
+    #[setter]
+    fn set_max_number_length(self_: PyRef<Self>, length: Option<usize>) {
+        setter!(self_, BpeTrainer, max_number_length, length);
+    }
+
     #[new]
     #[pyo3(signature = (**kwargs), text_signature = None)]
     pub fn new(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<(Self, PyTrainer)> {
@@ -358,6 +542,8 @@ impl PyBpeTrainer {
                         builder = builder.continuing_subword_prefix(val.extract()?)
                     }
                     "end_of_word_suffix" => builder = builder.end_of_word_suffix(val.extract()?),
+                    "min_token_length" => builder = builder.min_token_length(val.extract()?),
+                    "max_number_length" => builder = builder.max_number_length(val.extract()?),
                     _ => println!("Ignored unknown kwargs option {key}"),
                 };
             }
@@ -395,6 +581,17 @@ impl PyBpeTrainer {
 ///
 ///     end_of_word_suffix (:obj:`str`, `optional`):
 ///         A suffix to be used for every subword that is a end-of-word.
+///
+///     min_token_length (:obj:`int`, `optional`):
+///         Prevents creating tokens shorter than the specified size, symmetric to
+///         `max_token_length`.
+///
+///     max_number_length (:obj:`int`, `optional`):
+///         Caps the length of merged tokens made up entirely of digits.
+///
+///     use_likelihood_scoring (:obj:`bool`, `optional`):
+///         Merge the pair that maximizes corpus log-likelihood gain (the original WordPiece
+///         criterion) instead of the most frequent pair (the BPE approximation used by default).
 #[pyclass(extends=PyTrainer, module = "tokenizers.trainers", name = "WordPieceTrainer")]
 pub struct PyWordPieceTrainer {}
 #[pymethods]
@@ -515,6 +712,48 @@ impl PyWordPieceTrainer {
         setter!(self_, WordPieceTrainer, @set_end_of_word_suffix, suffix);
     }
 
+    /// Injects already-counted word frequencies directly into the trainer, bypassing
+    /// :meth:`~tokenizers.trainers.Trainer.feed` entirely.
+    ///
+    /// Args:
+    ///     word_counts (:obj:`Dict[str, int]`):
+    ///         A mapping of words to their frequency in the corpus. Counts must be non-negative.
+    fn word_counts(self_: PyRef<Self>, word_counts: HashMap<String, i64>) -> PyResult<()> {
+        let counts = validate_word_counts(word_counts)?;
+        setter!(self_, WordPieceTrainer, @feed_word_counts, counts);
+        Ok(())
+    }
+
+    #[getter]
+    fn get_min_token_length(self_: PyRef<Self>) -> Option<usize> {
+        getter!(self_, WordPieceTrainer, min_token_length())
+    }
+
+    #[setter]
+    fn set_min_token_length(self_: PyRef<Self>, length: Option<usize>) {
+        setter!(self_, WordPieceTrainer, @set_min_token_length, length);
+    }
+
+    #[getter]
+    fn get_max_number_length(self_: PyRef<Self>) -> Option<usize> {
+        getter!(self_, WordPieceTrainer, max_number_length())
+    }
+
+    #[setter]
+    fn set_max_number_length(self_: PyRef<Self>, length: Option<usize>) {
+        setter!(self_, WordPieceTrainer, @set_max_number_length, length);
+    }
+
+    #[getter]
+    fn get_use_likelihood_scoring(self_: PyRef<Self>) -> bool {
+        getter!(self_, WordPieceTrainer, use_likelihood_scoring())
+    }
+
+    #[setter]
+    fn set_use_likelihood_scoring(self_: PyRef<Self>, use_likelihood_scoring: bool) {
+        setter!(self_, WordPieceTrainer, @set_use_likelihood_scoring, use_likelihood_scoring);
+    }
+
     #[new]
     #[pyo3(
         signature = (** kwargs),
@@ -564,6 +803,11 @@ impl PyWordPieceTrainer {
                         builder = builder.continuing_subword_prefix(val.extract()?)
                     }
                     "end_of_word_suffix" => builder = builder.end_of_word_suffix(val.extract()?),
+                    "min_token_length" => builder = builder.min_token_length(val.extract()?),
+                    "max_number_length" => builder = builder.max_number_length(val.extract()?),
+                    "use_likelihood_scoring" => {
+                        builder = builder.use_likelihood_scoring(val.extract()?)
+                    }
                     _ => println!("Ignored unknown kwargs option {key}"),
                 };
             }
@@ -658,6 +902,18 @@ impl PyWordLevelTrainer {
         Ok(())
     }
 
+    /// Injects already-counted word frequencies directly into the trainer, bypassing
+    /// :meth:`~tokenizers.trainers.Trainer.feed` entirely.
+    ///
+    /// Args:
+    ///     word_counts (:obj:`Dict[str, int]`):
+    ///         A mapping of words to their frequency in the corpus. Counts must be non-negative.
+    fn word_counts(self_: PyRef<Self>, word_counts: HashMap<String, i64>) -> PyResult<()> {
+        let counts = validate_word_counts(word_counts)?;
+        setter!(self_, WordLevelTrainer, @feed_word_counts, counts);
+        Ok(())
+    }
+
     #[new]
     #[pyo3(signature = (**kwargs), text_signature = None)]
     pub fn new(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<(Self, PyTrainer)> {
@@ -743,6 +999,22 @@ impl PyWordLevelTrainer {
 ///     n_sub_iterations (:obj:`int`):
 ///         The number of iterations of the EM algorithm to perform before
 ///         pruning the vocabulary.
+///
+///     initial_vocab (:obj:`List[Tuple[str, float]]`, `optional`):
+///         A starting seed vocabulary of `(piece, log_prob)` pairs, used instead of generating
+///         seed pieces from the training corpus - lets an already-trained model be refined on new
+///         data instead of retrained from scratch. `initial_alphabet` and `special_tokens` are
+///         still unioned in so no required token is pruned.
+///
+///     limit_alphabet (:obj:`int`, `optional`):
+///         The maximum different characters to keep as single-character seed pieces, keeping the
+///         most frequent ones. `initial_alphabet` and `special_tokens` are always retained
+///         regardless of this cap.
+///
+///     byte_fallback (:obj:`bool`, `optional`):
+///         Whether to inject the 256 single-byte tokens into the vocabulary and decompose
+///         out-of-vocabulary characters into their UTF-8 bytes instead of mapping them to
+///         `unk_token`.
 #[pyclass(extends=PyTrainer, module = "tokenizers.trainers", name = "UnigramTrainer")]
 pub struct PyUnigramTrainer {}
 #[pymethods]
@@ -823,10 +1095,42 @@ impl PyUnigramTrainer {
         );
     }
 
+    #[getter]
+    fn get_limit_alphabet(self_: PyRef<Self>) -> Option<usize> {
+        getter!(self_, UnigramTrainer, limit_alphabet)
+    }
+
+    #[setter]
+    fn set_limit_alphabet(self_: PyRef<Self>, limit: Option<usize>) {
+        setter!(self_, UnigramTrainer, limit_alphabet, limit);
+    }
+
+    #[getter]
+    fn get_byte_fallback(self_: PyRef<Self>) -> bool {
+        getter!(self_, UnigramTrainer, byte_fallback)
+    }
+
+    #[setter]
+    fn set_byte_fallback(self_: PyRef<Self>, byte_fallback: bool) {
+        setter!(self_, UnigramTrainer, byte_fallback, byte_fallback);
+    }
+
+    /// Injects already-counted word frequencies directly into the trainer, bypassing
+    /// :meth:`~tokenizers.trainers.Trainer.feed` entirely.
+    ///
+    /// Args:
+    ///     word_counts (:obj:`Dict[str, int]`):
+    ///         A mapping of words to their frequency in the corpus. Counts must be non-negative.
+    fn word_counts(self_: PyRef<Self>, word_counts: HashMap<String, i64>) -> PyResult<()> {
+        let counts = validate_word_counts(word_counts)?;
+        setter!(self_, UnigramTrainer, @feed_word_counts, counts);
+        Ok(())
+    }
+
     #[new]
     #[pyo3(
         signature = (**kwargs),
-        text_signature = "(self, vocab_size=8000, show_progress=True, special_tokens=[], shrinking_factor=0.75, unk_token=None, max_piece_length=16, n_sub_iterations=2)"
+        text_signature = "(self, vocab_size=8000, show_progress=True, special_tokens=[], shrinking_factor=0.75, unk_token=None, max_piece_length=16, n_sub_iterations=2, initial_vocab=None, limit_alphabet=None, byte_fallback=False)"
     )]
     pub fn new(kwargs: Option<Bound<'_, PyDict>>) -> PyResult<(Self, PyTrainer)> {
         let mut builder = tk::models::unigram::UnigramTrainer::builder();
@@ -841,6 +1145,12 @@ impl PyUnigramTrainer {
                     "unk_token" => builder.unk_token(val.extract()?),
                     "max_piece_length" => builder.max_piece_length(val.extract()?),
                     "seed_size" => builder.seed_size(val.extract()?),
+                    "initial_vocab" => {
+                        let initial_vocab: Vec<(String, f64)> = val.extract()?;
+                        builder.initial_vocab(Some(initial_vocab))
+                    }
+                    "limit_alphabet" => builder.limit_alphabet(val.extract()?),
+                    "byte_fallback" => builder.byte_fallback(val.extract()?),
                     "initial_alphabet" => {
                         let alphabet: Vec<String> = val.extract()?;
                         builder.initial_alphabet(
@@ -885,6 +1195,257 @@ impl PyUnigramTrainer {
     }
 }
 
+/// Builds a blank model of the same family as `trainer`, ready to be trained in place.
+///
+/// `Unigram` has no empty-vocabulary state, so it is seeded with a single throwaway piece;
+/// `do_train` always replaces the vocab wholesale, so the seed never survives training.
+fn blank_model_for(trainer: &TrainerWrapper) -> PyResult<ModelWrapper> {
+    Ok(match trainer {
+        TrainerWrapper::BpeTrainer(_) => ModelWrapper::BPE(tk::models::bpe::BPE::default()),
+        TrainerWrapper::WordPieceTrainer(_) => ModelWrapper::WordPiece(
+            tk::models::wordpiece::WordPiece::from_bpe(&tk::models::bpe::BPE::default()),
+        ),
+        TrainerWrapper::WordLevelTrainer(_) => {
+            ModelWrapper::WordLevel(tk::models::wordlevel::WordLevel::default())
+        }
+        TrainerWrapper::UnigramTrainer(_) => ModelWrapper::Unigram(
+            tk::models::unigram::Unigram::new(vec![("<unk>".to_string(), 0.0)])
+                .map_err(|e| exceptions::PyException::new_err(e.to_string()))?,
+        ),
+    })
+}
+
+/// The short name a model family is reported under in [`PyMultiTrainer::train`]'s result.
+fn model_name_for(trainer: &TrainerWrapper) -> &'static str {
+    match trainer {
+        TrainerWrapper::BpeTrainer(_) => "bpe",
+        TrainerWrapper::WordPieceTrainer(_) => "wordpiece",
+        TrainerWrapper::WordLevelTrainer(_) => "wordlevel",
+        TrainerWrapper::UnigramTrainer(_) => "unigram",
+    }
+}
+
+/// Trains several models from a single shared pass over a corpus.
+///
+/// Comparing tokenizer families normally means iterating and counting the corpus once per
+/// trainer. `MultiTrainer` counts the (already pre-tokenized) words exactly once and fans the
+/// resulting frequency map out to every wrapped trainer, so the vocabularies it produces are
+/// built from identical statistics and the expensive counting step isn't repeated.
+///
+/// Args:
+///     trainers (:obj:`List[Trainer]`):
+///         The trainers to run. One model is produced per trainer.
+#[pyclass(module = "tokenizers.trainers", name = "MultiTrainer")]
+pub struct PyMultiTrainer {
+    trainers: Vec<Py<PyTrainer>>,
+}
+
+#[pymethods]
+impl PyMultiTrainer {
+    #[new]
+    #[pyo3(text_signature = "(self, trainers)")]
+    fn new(trainers: Vec<Py<PyTrainer>>) -> Self {
+        PyMultiTrainer { trainers }
+    }
+
+    /// Counts `words` once, then trains a fresh model for every trainer this `MultiTrainer` was
+    /// built with, each fed from that same shared frequency map.
+    ///
+    /// Args:
+    ///     words (:obj:`List[str]`):
+    ///         The already pre-tokenized words of the corpus (for example, the output of running
+    ///         a tokenizer's pre-tokenizer over each sequence). This does not itself run
+    ///         normalization or pre-tokenization.
+    ///
+    /// Returns:
+    ///     Dict[str, Tuple[Model, List[AddedToken]]]: one entry per trainer, keyed by model
+    ///     family (``"bpe"``, ``"wordpiece"``, ``"wordlevel"``, or ``"unigram"``), holding the
+    ///     trained model and the special tokens that were added.
+    fn train(&self, py: Python, words: Vec<String>) -> PyResult<PyObject> {
+        let mut word_counts: HashMap<String, u64> = HashMap::new();
+        for word in words {
+            *word_counts.entry(word).or_insert(0) += 1;
+        }
+        let counts: Vec<(String, u64)> = word_counts.into_iter().collect();
+
+        let result = PyDict::new(py);
+        for trainer in &self.trainers {
+            let trainer = trainer.borrow(py);
+
+            {
+                let mut trainer_guard = trainer.trainer.write().unwrap();
+                match &mut *trainer_guard {
+                    TrainerWrapper::BpeTrainer(t) => t.feed_word_counts(counts.clone()),
+                    TrainerWrapper::WordPieceTrainer(t) => t.feed_word_counts(counts.clone()),
+                    TrainerWrapper::WordLevelTrainer(t) => t.feed_word_counts(counts.clone()),
+                    TrainerWrapper::UnigramTrainer(t) => t.feed_word_counts(counts.clone()),
+                }
+            }
+
+            let trainer_guard = trainer.trainer.read().unwrap();
+            let mut model = blank_model_for(&trainer_guard)?;
+            let added_tokens = trainer_guard
+                .train(&mut model)
+                .map_err(|e| exceptions::PyException::new_err(e.to_string()))?;
+
+            let py_model: PyModel = model.into();
+            let py_added_tokens: Vec<PyAddedToken> =
+                added_tokens.into_iter().map(|tok| tok.into()).collect();
+            result.set_item(model_name_for(&trainer_guard), (py_model, py_added_tokens))?;
+        }
+
+        Ok(result.into())
+    }
+}
+
+/// Reads back `trainer`'s configured special tokens, regardless of which variant it is.
+fn special_tokens_of(trainer: &TrainerWrapper) -> Vec<tk::tokenizer::AddedToken> {
+    match trainer {
+        TrainerWrapper::BpeTrainer(t) => t.special_tokens.clone(),
+        TrainerWrapper::WordPieceTrainer(t) => t.special_tokens().to_vec(),
+        TrainerWrapper::WordLevelTrainer(t) => t.special_tokens.clone(),
+        TrainerWrapper::UnigramTrainer(t) => t.special_tokens.clone(),
+    }
+}
+
+/// Wraps several sub-trainers - any mix of BPE / WordPiece / WordLevel / Unigram, each already
+/// configured with its own kwargs - trains them over the same corpus in one counting pass, and
+/// merges their vocabularies into a single ranked, deduplicated `WordLevel` model. Useful for
+/// broad-coverage vocabularies (e.g. smartlist-style token sets for fuzzing/wordlist generation)
+/// where any single algorithm under-covers the space.
+///
+/// A merged token's frequency is the total corpus-word frequency of every input word that
+/// contains it as a substring; this is an approximation (most sub-trainer pieces are not whole
+/// words) but gives a stable, explainable ranking for the union.
+///
+/// Args:
+///     trainers (:obj:`List[Trainer]`):
+///         The sub-trainers to run, each already configured with its own kwargs.
+///     vocab_size (:obj:`int`, `optional`):
+///         Caps the merged vocabulary to this many tokens, keeping the most frequent ones. By
+///         default the merge is unbounded.
+///     min_word_len (:obj:`int`, `optional`):
+///         Drops merged tokens shorter than this many characters. Applied uniformly across all
+///         sub-trainers' outputs, not per sub-trainer.
+///     numbers_max_size (:obj:`int`, `optional`):
+///         Drops merged tokens made up entirely of digits that are longer than this many
+///         characters. Applied uniformly across all sub-trainers' outputs, not per sub-trainer.
+#[pyclass(module = "tokenizers.trainers", name = "EnsembleTrainer")]
+pub struct PyEnsembleTrainer {
+    trainers: Vec<Py<PyTrainer>>,
+    vocab_size: Option<usize>,
+    min_word_len: Option<usize>,
+    numbers_max_size: Option<usize>,
+}
+
+#[pymethods]
+impl PyEnsembleTrainer {
+    #[new]
+    #[pyo3(signature = (trainers, vocab_size=None, min_word_len=None, numbers_max_size=None))]
+    fn new(
+        trainers: Vec<Py<PyTrainer>>,
+        vocab_size: Option<usize>,
+        min_word_len: Option<usize>,
+        numbers_max_size: Option<usize>,
+    ) -> Self {
+        PyEnsembleTrainer {
+            trainers,
+            vocab_size,
+            min_word_len,
+            numbers_max_size,
+        }
+    }
+
+    /// Counts `words` once, trains every sub-trainer into a blank model of its own family fed
+    /// from that shared count, then merges all resulting vocabularies into a single ranked
+    /// `WordLevel` model.
+    ///
+    /// Args:
+    ///     words (:obj:`List[str]`):
+    ///         The already pre-tokenized words of the corpus.
+    ///
+    /// Returns:
+    ///     Model: a `WordLevel` model holding the merged, deduplicated, frequency-ranked
+    ///     vocabulary across every sub-trainer.
+    fn train(&self, py: Python, words: Vec<String>) -> PyResult<PyModel> {
+        let mut word_counts: HashMap<String, u64> = HashMap::new();
+        for word in words {
+            *word_counts.entry(word).or_insert(0) += 1;
+        }
+        let counts: Vec<(String, u64)> = word_counts.iter().map(|(w, c)| (w.clone(), *c)).collect();
+
+        let mut merged: HashMap<String, u64> = HashMap::new();
+        let mut all_special_tokens: Vec<tk::tokenizer::AddedToken> = Vec::new();
+        let mut seen_special: HashSet<String> = HashSet::new();
+
+        for trainer in &self.trainers {
+            let trainer = trainer.borrow(py);
+
+            {
+                let mut trainer_guard = trainer.trainer.write().unwrap();
+                match &mut *trainer_guard {
+                    TrainerWrapper::BpeTrainer(t) => t.feed_word_counts(counts.clone()),
+                    TrainerWrapper::WordPieceTrainer(t) => t.feed_word_counts(counts.clone()),
+                    TrainerWrapper::WordLevelTrainer(t) => t.feed_word_counts(counts.clone()),
+                    TrainerWrapper::UnigramTrainer(t) => t.feed_word_counts(counts.clone()),
+                }
+            }
+
+            let trainer_guard = trainer.trainer.read().unwrap();
+
+            for token in special_tokens_of(&trainer_guard) {
+                if seen_special.insert(token.content.clone()) {
+                    all_special_tokens.push(token);
+                }
+            }
+
+            let mut model = blank_model_for(&trainer_guard)?;
+            trainer_guard
+                .train(&mut model)
+                .map_err(|e| exceptions::PyException::new_err(e.to_string()))?;
+
+            for (token, _id) in model.get_vocab() {
+                if merged.contains_key(&token) {
+                    continue;
+                }
+                if token.chars().count() < self.min_word_len.unwrap_or(0) {
+                    continue;
+                }
+                if let Some(cap) = self.numbers_max_size {
+                    if !token.is_empty()
+                        && token.chars().all(|c| c.is_ascii_digit())
+                        && token.chars().count() > cap
+                    {
+                        continue;
+                    }
+                }
+
+                let frequency: u64 = word_counts
+                    .iter()
+                    .filter(|(word, _)| word.contains(token.as_str()))
+                    .map(|(_, count)| *count)
+                    .sum();
+                *merged.entry(token).or_insert(0) += frequency;
+            }
+        }
+
+        let mut builder = tk::models::wordlevel::WordLevelTrainer::builder();
+        builder.vocab_size(self.vocab_size.unwrap_or(usize::MAX));
+        builder.special_tokens(all_special_tokens);
+        let mut merge_trainer = builder
+            .build()
+            .map_err(|e| exceptions::PyException::new_err(e.to_string()))?;
+        merge_trainer.feed_word_counts(merged);
+
+        let mut merged_model = tk::models::wordlevel::WordLevel::default();
+        merge_trainer
+            .train(&mut merged_model)
+            .map_err(|e| exceptions::PyException::new_err(e.to_string()))?;
+
+        Ok(ModelWrapper::WordLevel(merged_model).into())
+    }
+}
+
 /// Trainers Module
 #[pymodule]
 pub fn trainers(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -893,6 +1454,8 @@ pub fn trainers(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyWordPieceTrainer>()?;
     m.add_class::<PyWordLevelTrainer>()?;
     m.add_class::<PyUnigramTrainer>()?;
+    m.add_class::<PyMultiTrainer>()?;
+    m.add_class::<PyEnsembleTrainer>()?;
     Ok(())
 }
 
@@ -909,4 +1472,19 @@ mod tests {
             assert_eq!("BpeTrainer", py_bpe.bind(py).get_type().qualname().unwrap());
         })
     }
+
+    #[test]
+    fn validate_word_counts_rejects_negative_counts() {
+        let mut word_counts = HashMap::new();
+        word_counts.insert("hello".to_string(), -1);
+        assert!(validate_word_counts(word_counts).is_err());
+    }
+
+    #[test]
+    fn validate_word_counts_accepts_non_negative_counts() {
+        let mut word_counts = HashMap::new();
+        word_counts.insert("hello".to_string(), 3);
+        let counts = validate_word_counts(word_counts).unwrap();
+        assert_eq!(counts, vec![("hello".to_string(), 3)]);
+    }
 }
@@ -3,6 +3,7 @@ use crate::tokenizer::{Model, Result, Token};
 use crate::utils::cache::{Cache, DEFAULT_CACHE_CAPACITY, MAX_LENGTH};
 use crate::utils::iter::ResultShunt;
 use ahash::AHashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::borrow::Cow;
 
@@ -19,6 +20,41 @@ type VocabR = AHashMap<u32, String>;
 pub type MergeMap = AHashMap<Pair, (u32, u32)>;
 pub type Merges = Vec<(String, String)>;
 
+/// A single entry of a serialized merges list.
+///
+/// Historically each merge was stored as a single `"left right"` string, which is ambiguous
+/// whenever `left` or `right` themselves contain a space. Newer files store the pair as an
+/// explicit two-element array instead. Both forms are accepted on load; only the array form is
+/// emitted when serializing (the `merges.txt` written by `save` keeps the space-separated form).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum MergeEntry {
+    Legacy(String),
+    Pair(String, String),
+}
+
+impl MergeEntry {
+    fn into_pair(self) -> (String, String) {
+        match self {
+            // Legacy form: split on the first whitespace, as the merges.txt format does.
+            MergeEntry::Legacy(s) => match s.split_once(' ') {
+                Some((a, b)) => (a.to_string(), b.to_string()),
+                None => (s, String::new()),
+            },
+            MergeEntry::Pair(a, b) => (a, b),
+        }
+    }
+}
+
+pub(crate) fn deserialize_merges<'de, D>(deserializer: D) -> std::result::Result<Merges, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let entries = Vec::<MergeEntry>::deserialize(deserializer)?;
+    Ok(entries.into_iter().map(MergeEntry::into_pair).collect())
+}
+
+
 struct Config {
     files: Option<(String, String)>,
     vocab: Vocab,
@@ -281,6 +317,97 @@ impl Clone for BPE {
     }
 }
 
+impl Serialize for BPE {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut model = serializer.serialize_struct("BPE", 9)?;
+        model.serialize_field("type", "BPE")?;
+        model.serialize_field("dropout", &self.dropout)?;
+        model.serialize_field("unk_token", &self.unk_token)?;
+        model.serialize_field(
+            "continuing_subword_prefix",
+            &self.continuing_subword_prefix,
+        )?;
+        model.serialize_field("end_of_word_suffix", &self.end_of_word_suffix)?;
+        model.serialize_field("fuse_unk", &self.fuse_unk)?;
+        model.serialize_field("byte_fallback", &self.byte_fallback)?;
+        model.serialize_field("ignore_merges", &self.ignore_merges)?;
+        model.serialize_field("vocab", &OrderedVocabIter::new(&self.vocab_r))?;
+
+        // Merges are emitted in rank order as `[left, right]` pairs so that pieces containing a
+        // space character round-trip losslessly (see `deserialize_merges` for the read side).
+        let mut ranked_pairs: Vec<(&Pair, &u32)> =
+            self.merges.iter().map(|(pair, (rank, _))| (pair, rank)).collect();
+        ranked_pairs.sort_unstable_by_key(|(_, rank)| **rank);
+        let merges: Vec<(&str, &str)> = ranked_pairs
+            .into_iter()
+            .map(|(pair, _)| {
+                (
+                    self.vocab_r[&pair.0].as_str(),
+                    self.vocab_r[&pair.1].as_str(),
+                )
+            })
+            .collect();
+        model.serialize_field("merges", &merges)?;
+
+        model.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BPE {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct BPEHelper {
+            #[serde(default)]
+            dropout: Option<f32>,
+            #[serde(default)]
+            unk_token: Option<String>,
+            #[serde(default)]
+            continuing_subword_prefix: Option<String>,
+            #[serde(default)]
+            end_of_word_suffix: Option<String>,
+            #[serde(default)]
+            fuse_unk: bool,
+            #[serde(default)]
+            byte_fallback: bool,
+            #[serde(default)]
+            ignore_merges: bool,
+            vocab: HashMap<String, u32>,
+            #[serde(deserialize_with = "deserialize_merges")]
+            merges: Merges,
+        }
+
+        let helper = BPEHelper::deserialize(deserializer)?;
+        let vocab: Vocab = helper.vocab.into_iter().collect();
+        let mut builder = BPE::builder().vocab_and_merges(vocab, helper.merges);
+        if let Some(dropout) = helper.dropout {
+            builder = builder.dropout(dropout);
+        }
+        if let Some(unk_token) = helper.unk_token {
+            builder = builder.unk_token(unk_token);
+        }
+        if let Some(prefix) = helper.continuing_subword_prefix {
+            builder = builder.continuing_subword_prefix(prefix);
+        }
+        if let Some(suffix) = helper.end_of_word_suffix {
+            builder = builder.end_of_word_suffix(suffix);
+        }
+        builder
+            .fuse_unk(helper.fuse_unk)
+            .byte_fallback(helper.byte_fallback)
+            .ignore_merges(helper.ignore_merges)
+            .build()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 /// Converts the merges strings (for example from `merges.txt` file) with the format
 /// "{pair_a} {pair_b}" into the format expected by the BPE struct
 pub(crate) fn convert_merges_to_hashmap<I: Iterator<Item = String>>(
@@ -360,9 +487,10 @@ impl BPE {
         }
     }
 
-    /// Resize the cache
-    pub fn resize_cache(&mut self, capacity: usize) {
-        if let Some(ref mut cache) = self.cache {
+    /// Resize the cache. Safe to call from any thread that holds a shared reference to the
+    /// model, same as `clear_cache` and `tokenize`.
+    pub fn resize_cache(&self, capacity: usize) {
+        if let Some(ref cache) = self.cache {
             cache.resize(capacity);
         }
     }
@@ -582,6 +710,119 @@ mod tests {
     use super::*;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_bpe_with_end_of_word_suffix() {
+        let vocab: Vocab = vec![
+            ("a".to_string(), 0),
+            ("b</w>".to_string(), 1),
+            ("ab</w>".to_string(), 2),
+        ]
+        .into_iter()
+        .collect();
+
+        let merges = vec![("a".to_string(), "b</w>".to_string())];
+
+        let bpe = BPE::builder()
+            .vocab_and_merges(vocab, merges)
+            .end_of_word_suffix("</w>".to_string())
+            .build()
+            .unwrap();
+
+        // The suffix is only used for vocab lookup; reported offsets stay in terms of the
+        // original input bytes.
+        let res = bpe.tokenize("ab").unwrap();
+        assert_eq!(
+            res,
+            vec![Token {
+                id: 2,
+                value: "ab</w>".to_string(),
+                offsets: (0, 2)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_bpe_fuse_unk_keeps_correct_offsets_with_end_of_word_suffix() {
+        let vocab: Vocab = [("<unk>".into(), 0), ("a".into(), 1)].iter().cloned().collect();
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, vec![])
+            .unk_token("<unk>".to_string())
+            .end_of_word_suffix("</w>".to_string())
+            .fuse_unk(true)
+            .build()
+            .unwrap();
+
+        let tokens = bpe.tokenize("acc").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(1u32, "a".into(), (0, 1)),
+                Token::new(0u32, "<unk>".into(), (1, 3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cache_capacity_zero_disables_cache() {
+        let bpe = BPE::builder().cache_capacity(0).build().unwrap();
+        // No cache means clear_cache/resize_cache are no-ops instead of panicking.
+        bpe.clear_cache();
+        bpe.resize_cache(10);
+    }
+
+    #[test]
+    fn test_cache_is_shareable_behind_shared_reference() {
+        // Mirrors how a JNI call site would use the model concurrently: only `&BPE` is needed,
+        // never `&mut BPE`, for tokenizing, clearing and resizing the cache.
+        let bpe = BPE::new(
+            [("a".into(), 0), ("b".into(), 1), ("ab".into(), 2)]
+                .iter()
+                .cloned()
+                .collect(),
+            vec![("a".to_string(), "b".to_string())],
+        );
+        let shared: &BPE = &bpe;
+        assert!(shared.tokenize("ab").is_ok());
+        shared.clear_cache();
+        shared.resize_cache(1);
+    }
+
+    #[test]
+    fn test_merges_roundtrip_with_space_in_token() {
+        let vocab: Vocab = [("a".into(), 0), (" b".into(), 1), ("a b".into(), 2)]
+            .iter()
+            .cloned()
+            .collect();
+        let merges: Merges = vec![("a".to_string(), " b".to_string())];
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, merges)
+            .build()
+            .unwrap();
+
+        let serialized = serde_json::to_string(&bpe).unwrap();
+        assert!(serialized.contains("[\"a\",\" b\"]"));
+
+        let deserialized: BPE = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.merges.get(&(0, 1)).unwrap(), &(0u32, 2u32));
+    }
+
+    #[test]
+    fn test_merges_legacy_string_form_still_loads() {
+        let json = serde_json::json!({
+            "dropout": null,
+            "unk_token": null,
+            "continuing_subword_prefix": null,
+            "end_of_word_suffix": null,
+            "fuse_unk": false,
+            "byte_fallback": false,
+            "ignore_merges": false,
+            "vocab": {"a": 0, "b": 1, "ab": 2},
+            "merges": ["a b"],
+        });
+        let bpe: BPE = serde_json::from_value(json).unwrap();
+        assert_eq!(bpe.merges.get(&(0, 1)).unwrap(), &(0u32, 2u32));
+    }
+
     #[test]
     fn test_ordered_vocab_iter() {
         let vocab_r: VocabR = [
@@ -733,6 +974,21 @@ mod tests {
         assert!(!tokens.is_empty() && tokens.len() <= 9);
     }
 
+    #[test]
+    // `BPE::from_file` must not touch the filesystem until `build()` is called, so options can
+    // be chained onto it exactly like the in-memory `vocab_and_merges` flow.
+    fn test_bpe_from_file_defers_io_to_build() {
+        let builder = BPE::from_file("/does/not/exist-vocab.json", "/does/not/exist-merges.txt")
+            .dropout(0.1)
+            .unk_token("[UNK]".to_string());
+
+        let err = builder.build().unwrap_err();
+        let io_err = err
+            .downcast_ref::<std::io::Error>()
+            .expect("expected the file-not-found error to surface as a std::io::Error");
+        assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+    }
+
     #[test]
     // Ensure `BPE::from_file` works as expected.
     fn test_bpe_from_file() {
@@ -0,0 +1,742 @@
+use super::{Pair, Word, BPE};
+use crate::tokenizer::{AddedToken, Result, Trainer};
+use crate::utils::progress::{ProgressBar, ProgressStyle};
+use ahash::{AHashMap, AHashSet};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::Arc;
+
+#[derive(Debug, Eq)]
+struct Merge {
+    pair: Pair,
+    count: u64,
+    pos: HashSet<usize>,
+}
+impl PartialEq for Merge {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.pair == other.pair
+    }
+}
+impl PartialOrd for Merge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Merge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.count != other.count {
+            self.count.cmp(&other.count)
+        } else {
+            // Self-reverse the pair ordering so that ties are broken deterministically rather
+            // than depending on `BinaryHeap`'s unspecified order among equal elements.
+            other.pair.cmp(&self.pair)
+        }
+    }
+}
+
+/// Structured progress event emitted by [`BpeTrainer::do_train`] (and reused by
+/// [`WordPieceTrainer`](super::super::wordpiece::WordPieceTrainer), which wraps this trainer) for
+/// callers that want to observe training beyond the plain `ProgressBar`: notebooks, services, or
+/// JNI callers that want to stream metrics or abort early.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrainerEvent {
+    /// The initial vocabulary (special tokens + alphabet) has been computed, before any merges.
+    AlphabetComputed {
+        /// Number of tokens in the initial vocabulary.
+        size: usize,
+    },
+    /// The corpus has been counted into distinct words.
+    CountingComplete {
+        /// Number of distinct words seen.
+        unique_words: usize,
+    },
+    /// A merge was just applied.
+    MergeProgress {
+        /// Current vocabulary size, including the token this merge just created.
+        vocab_size: usize,
+        /// Total number of merges applied so far.
+        merges_applied: usize,
+        /// The two tokens that were merged.
+        last_pair: (String, String),
+        /// The pair's frequency at the time it was merged.
+        frequency: u64,
+    },
+}
+
+/// A structured summary of a [`BpeTrainer::do_train_with_report`] run: the initial alphabet that
+/// was actually kept (after `limit_alphabet`/`initial_alphabet`), the merges applied in order
+/// together with the frequency that triggered each, and how many candidate merges were rejected
+/// for exceeding `max_token_length`. Useful for vocabulary auditing, reproducibility diffing
+/// between runs, and tuning `min_frequency`/`vocab_size` without black-box guesswork.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrainingReport {
+    /// The initial alphabet, after `limit_alphabet`/`initial_alphabet` were applied. Does not
+    /// include special tokens.
+    pub alphabet: Vec<String>,
+    /// The merges applied, in the order they were applied, paired with the pair frequency that
+    /// triggered each one.
+    pub merges: Vec<((String, String), u64)>,
+    /// Number of candidate merges that were skipped because the resulting token exceeded
+    /// `max_token_length`.
+    pub dropped_by_max_token_length: usize,
+}
+
+/// A boxed [`TrainerEvent`] callback, wrapped so it can sit inside the otherwise
+/// `Debug`/`Clone`/`Serialize`-derived [`BpeTrainer`]; the callback itself is opaque and not
+/// persisted across a pickle round-trip.
+#[derive(Clone)]
+pub struct TrainerCallback(Arc<dyn Fn(TrainerEvent) + Send + Sync>);
+
+impl TrainerCallback {
+    pub fn new(callback: impl Fn(TrainerEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+}
+
+impl std::fmt::Debug for TrainerCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TrainerCallback(..)")
+    }
+}
+
+/// A [`BpeTrainer`] configuration builder.
+///
+/// Constructed via [`BpeTrainer::builder`], mirroring the rest of this crate's model/trainer pairs.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct BpeTrainerBuilder {
+    config: BpeTrainerConfig,
+}
+
+#[derive(Debug, Clone)]
+struct BpeTrainerConfig {
+    min_frequency: u64,
+    vocab_size: usize,
+    show_progress: bool,
+    special_tokens: Vec<AddedToken>,
+    limit_alphabet: Option<usize>,
+    initial_alphabet: AHashSet<char>,
+    continuing_subword_prefix: Option<String>,
+    end_of_word_suffix: Option<String>,
+    max_token_length: Option<usize>,
+    min_token_length: Option<usize>,
+    max_number_length: Option<usize>,
+    callback: Option<TrainerCallback>,
+}
+
+impl Default for BpeTrainerConfig {
+    fn default() -> Self {
+        Self {
+            min_frequency: 0,
+            vocab_size: 30000,
+            show_progress: true,
+            special_tokens: vec![],
+            limit_alphabet: None,
+            initial_alphabet: AHashSet::new(),
+            continuing_subword_prefix: None,
+            end_of_word_suffix: None,
+            max_token_length: None,
+            min_token_length: None,
+            max_number_length: None,
+            callback: None,
+        }
+    }
+}
+
+impl Default for BpeTrainerBuilder {
+    fn default() -> Self {
+        Self {
+            config: BpeTrainerConfig::default(),
+        }
+    }
+}
+
+impl BpeTrainerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min_frequency(mut self, frequency: u64) -> Self {
+        self.config.min_frequency = frequency;
+        self
+    }
+
+    pub fn vocab_size(mut self, vocab_size: usize) -> Self {
+        self.config.vocab_size = vocab_size;
+        self
+    }
+
+    pub fn show_progress(mut self, show_progress: bool) -> Self {
+        self.config.show_progress = show_progress;
+        self
+    }
+
+    pub fn special_tokens(mut self, special_tokens: Vec<AddedToken>) -> Self {
+        self.config.special_tokens = special_tokens;
+        self
+    }
+
+    pub fn limit_alphabet(mut self, limit: usize) -> Self {
+        self.config.limit_alphabet = Some(limit);
+        self
+    }
+
+    pub fn initial_alphabet(mut self, alphabet: AHashSet<char>) -> Self {
+        self.config.initial_alphabet = alphabet;
+        self
+    }
+
+    pub fn continuing_subword_prefix(mut self, prefix: String) -> Self {
+        self.config.continuing_subword_prefix = Some(prefix);
+        self
+    }
+
+    pub fn end_of_word_suffix(mut self, suffix: String) -> Self {
+        self.config.end_of_word_suffix = Some(suffix);
+        self
+    }
+
+    pub fn max_token_length(mut self, max_token_length: Option<usize>) -> Self {
+        self.config.max_token_length = max_token_length;
+        self
+    }
+
+    /// Rejects merges that would produce a token shorter than `min_token_length`, symmetric to
+    /// [`Self::max_token_length`]. The merge is simply skipped; its sub-pieces remain free to
+    /// merge elsewhere.
+    pub fn min_token_length(mut self, min_token_length: Option<usize>) -> Self {
+        self.config.min_token_length = min_token_length;
+        self
+    }
+
+    /// Caps the length of merged tokens that consist entirely of ASCII digits, independently of
+    /// `max_token_length`, so a vocabulary used as a wordlist isn't polluted by long numeric runs.
+    pub fn max_number_length(mut self, max_number_length: Option<usize>) -> Self {
+        self.config.max_number_length = max_number_length;
+        self
+    }
+
+    /// Registers a callback invoked with structured [`TrainerEvent`]s during
+    /// [`BpeTrainer::do_train`] (alphabet computed, counting complete, per-merge progress).
+    pub fn callback(mut self, callback: TrainerCallback) -> Self {
+        self.config.callback = Some(callback);
+        self
+    }
+
+    pub fn build(self) -> BpeTrainer {
+        BpeTrainer {
+            min_frequency: self.config.min_frequency,
+            vocab_size: self.config.vocab_size,
+            show_progress: self.config.show_progress,
+            special_tokens: self.config.special_tokens,
+            limit_alphabet: self.config.limit_alphabet,
+            initial_alphabet: self.config.initial_alphabet,
+            continuing_subword_prefix: self.config.continuing_subword_prefix,
+            end_of_word_suffix: self.config.end_of_word_suffix,
+            max_token_length: self.config.max_token_length,
+            min_token_length: self.config.min_token_length,
+            max_number_length: self.config.max_number_length,
+            callback: self.config.callback,
+            words: AHashMap::new(),
+        }
+    }
+}
+
+/// In charge of training a [`BPE`](super::BPE) model from word-frequency counts.
+///
+/// Instances are normally fed through [`Trainer::feed`], which pre-tokenizes and counts a raw
+/// corpus, but the internal `words` map can also be populated directly (see
+/// [`BpeTrainer::feed_word_counts`]) when the counting has already been done elsewhere.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BpeTrainer {
+    /// The minimum frequency a pair must have to produce a merge.
+    pub min_frequency: u64,
+    /// The size of the final vocabulary, including all tokens and alphabet.
+    pub vocab_size: usize,
+    /// Whether to show progress while training.
+    pub show_progress: bool,
+    /// A list of special tokens the model should know of.
+    pub special_tokens: Vec<AddedToken>,
+    /// Whether to limit the number of initial tokens that can be kept before computing merges.
+    pub limit_alphabet: Option<usize>,
+    /// A set of characters to include in the initial alphabet, even if not seen in the training
+    /// dataset.
+    pub initial_alphabet: AHashSet<char>,
+    /// An optional prefix to use on any subword that is not a beginning-of-word.
+    pub continuing_subword_prefix: Option<String>,
+    /// An optional suffix to use on any subword that is an end-of-word.
+    pub end_of_word_suffix: Option<String>,
+    /// Prevents creating tokens longer than the specified size.
+    pub max_token_length: Option<usize>,
+    /// Prevents creating tokens shorter than the specified size.
+    pub min_token_length: Option<usize>,
+    /// Caps the length of a merged token made up entirely of ASCII digits.
+    pub max_number_length: Option<usize>,
+    /// An optional callback invoked with structured [`TrainerEvent`]s during training. Not
+    /// persisted across a pickle round-trip.
+    #[serde(skip)]
+    callback: Option<TrainerCallback>,
+
+    words: AHashMap<String, u64>,
+}
+
+impl Default for BpeTrainer {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Compares configuration and accumulated word counts; the `callback`, if any, is opaque and
+/// excluded, the same way it is skipped when (de)serializing.
+impl PartialEq for BpeTrainer {
+    fn eq(&self, other: &Self) -> bool {
+        self.min_frequency == other.min_frequency
+            && self.vocab_size == other.vocab_size
+            && self.show_progress == other.show_progress
+            && self.special_tokens == other.special_tokens
+            && self.limit_alphabet == other.limit_alphabet
+            && self.initial_alphabet == other.initial_alphabet
+            && self.continuing_subword_prefix == other.continuing_subword_prefix
+            && self.end_of_word_suffix == other.end_of_word_suffix
+            && self.max_token_length == other.max_token_length
+            && self.min_token_length == other.min_token_length
+            && self.max_number_length == other.max_number_length
+            && self.words == other.words
+    }
+}
+impl Eq for BpeTrainer {}
+
+impl BpeTrainer {
+    pub fn new(min_frequency: u64, vocab_size: usize) -> Self {
+        Self::builder()
+            .min_frequency(min_frequency)
+            .vocab_size(vocab_size)
+            .build()
+    }
+
+    pub fn builder() -> BpeTrainerBuilder {
+        BpeTrainerBuilder::new()
+    }
+
+    /// Merges `counts` into the trainer's internal word-frequency map, bypassing
+    /// [`Trainer::feed`] entirely. Useful for re-sweeping `vocab_size`/`min_frequency` against a
+    /// cached frequency table, or for merging counts accumulated across corpus shards.
+    pub fn feed_word_counts(&mut self, counts: impl IntoIterator<Item = (String, u64)>) {
+        for (word, count) in counts {
+            *self.words.entry(word).or_insert(0) += count;
+        }
+    }
+
+    /// The trainer's internal word-frequency map, exposed so wrapper trainers (e.g.
+    /// [`super::super::wordpiece::WordPieceTrainer`], which reuses this trainer internally) can
+    /// drive their own [`Trainer::train`] from it.
+    pub(crate) fn words(&self) -> &AHashMap<String, u64> {
+        &self.words
+    }
+
+    /// The trainer's currently registered progress callback, if any.
+    pub fn callback(&self) -> Option<&TrainerCallback> {
+        self.callback.as_ref()
+    }
+
+    /// Registers, or clears, the callback invoked with structured [`TrainerEvent`]s during
+    /// [`Self::do_train`].
+    pub fn set_callback(&mut self, callback: Option<TrainerCallback>) {
+        self.callback = callback;
+    }
+
+    pub(crate) fn emit(&self, event: TrainerEvent) {
+        if let Some(TrainerCallback(callback)) = &self.callback {
+            callback(event);
+        }
+    }
+
+    fn is_within_token_bounds(&self, token: &str) -> bool {
+        let len = token.chars().count();
+        if let Some(max) = self.max_token_length {
+            if len > max {
+                return false;
+            }
+        }
+        if let Some(min) = self.min_token_length {
+            if len < min {
+                return false;
+            }
+        }
+        if let Some(max_number_length) = self.max_number_length {
+            if len > max_number_length
+                && !token.is_empty()
+                && token.chars().all(|c| c.is_ascii_digit())
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn setup_progress(&self) -> Option<ProgressBar> {
+        if self.show_progress {
+            let p = ProgressBar::new(0);
+            p.set_style(
+                ProgressStyle::default_bar()
+                    .template("[{elapsed_precise}] {msg:<30!} {wide_bar} {pos:<9!}/{len:>9!}"),
+            );
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn add_special_tokens(&self, w2id: &mut AHashMap<String, u32>, id2w: &mut Vec<String>) {
+        for token in &self.special_tokens {
+            if !w2id.contains_key(&token.content) {
+                id2w.push(token.content.to_owned());
+                w2id.insert(token.content.to_owned(), (id2w.len() - 1) as u32);
+            }
+        }
+    }
+
+    pub(crate) fn compute_alphabet(
+        &self,
+        wc: &AHashMap<String, u64>,
+        w2id: &mut AHashMap<String, u32>,
+        id2w: &mut Vec<String>,
+    ) {
+        let mut alphabet: AHashMap<char, usize> = AHashMap::new();
+        for (word, count) in wc {
+            for c in word.chars() {
+                alphabet
+                    .entry(c)
+                    .and_modify(|cnt| *cnt += *count as usize)
+                    .or_insert(*count as usize);
+            }
+        }
+        for c in &self.initial_alphabet {
+            alphabet.entry(*c).or_insert(usize::MAX);
+        }
+
+        let mut kept = alphabet.iter().collect::<Vec<_>>();
+        if let Some(limit) = self.limit_alphabet {
+            kept.sort_unstable_by_key(|(_, freq)| std::cmp::Reverse(**freq));
+            kept.truncate(limit);
+        }
+        kept.sort_unstable_by_key(|(c, _)| **c);
+
+        for (c, _) in kept {
+            let s = c.to_string();
+            if !w2id.contains_key(&s) {
+                id2w.push(s.clone());
+                w2id.insert(s, (id2w.len() - 1) as u32);
+            }
+        }
+    }
+
+    pub(crate) fn tokenize_words(
+        &self,
+        wc: &AHashMap<String, u64>,
+        w2id: &mut AHashMap<String, u32>,
+        id2w: &mut Vec<String>,
+        p: &Option<ProgressBar>,
+    ) -> (Vec<Word>, Vec<u64>) {
+        let mut words: Vec<Word> = Vec::with_capacity(wc.len());
+        let mut counts: Vec<u64> = Vec::with_capacity(wc.len());
+
+        for (word, count) in wc {
+            let mut current_word = Word::new();
+            counts.push(*count);
+
+            for (is_first, is_last, c) in word.chars().with_first_last() {
+                let mut s = c.to_string();
+                if w2id.contains_key(&s) {
+                    if !is_first {
+                        if let Some(prefix) = &self.continuing_subword_prefix {
+                            s = format!("{prefix}{s}");
+                        }
+                    }
+                    if is_last {
+                        if let Some(suffix) = &self.end_of_word_suffix {
+                            s = format!("{s}{suffix}");
+                        }
+                    }
+
+                    if !w2id.contains_key(&s) {
+                        id2w.push(s.clone());
+                        w2id.insert(s.clone(), (id2w.len() - 1) as u32);
+                    }
+                    current_word.add(w2id[&s], s.chars().count());
+                }
+            }
+            words.push(current_word);
+
+            if let Some(p) = p {
+                p.inc(1);
+            }
+        }
+
+        (words, counts)
+    }
+
+    fn count_pairs(
+        &self,
+        words: &[Word],
+        counts: &[u64],
+        p: &Option<ProgressBar>,
+    ) -> (AHashMap<Pair, i64>, AHashMap<Pair, HashSet<usize>>) {
+        let mut pair_counts: AHashMap<Pair, i64> = AHashMap::new();
+        let mut where_to_update: AHashMap<Pair, HashSet<usize>> = AHashMap::new();
+
+        for (i, word) in words.iter().enumerate() {
+            for window in word.get_chars().windows(2) {
+                let pair: Pair = (window[0], window[1]);
+                *pair_counts.entry(pair).or_insert(0) += counts[i] as i64;
+                where_to_update.entry(pair).or_default().insert(i);
+            }
+            if let Some(p) = p {
+                p.inc(1);
+            }
+        }
+
+        (pair_counts, where_to_update)
+    }
+
+    pub fn do_train(
+        &self,
+        word_counts: &AHashMap<String, u64>,
+        model: &mut BPE,
+    ) -> Result<Vec<AddedToken>> {
+        self.do_train_with_report(word_counts, model)
+            .map(|(added_tokens, _report)| added_tokens)
+    }
+
+    /// Trains `model` from the trainer's internal word-frequency map (the same source
+    /// [`Trainer::train`] uses), additionally returning a [`TrainingReport`]. Exposed as an
+    /// inherent method, rather than only through the `Trainer` trait, so callers that want the
+    /// report don't need a trait object just to reach it.
+    pub fn train_with_report(&self, model: &mut BPE) -> Result<(Vec<AddedToken>, TrainingReport)> {
+        self.do_train_with_report(&self.words, model)
+    }
+
+    /// Same as [`Self::do_train`], but additionally returns a [`TrainingReport`] describing the
+    /// alphabet that was kept and every merge that was applied.
+    pub fn do_train_with_report(
+        &self,
+        word_counts: &AHashMap<String, u64>,
+        model: &mut BPE,
+    ) -> Result<(Vec<AddedToken>, TrainingReport)> {
+        let mut word_to_id: AHashMap<String, u32> = AHashMap::with_capacity(self.vocab_size);
+        let mut id_to_word: Vec<String> = Vec::with_capacity(self.vocab_size);
+
+        let progress = self.setup_progress();
+
+        self.add_special_tokens(&mut word_to_id, &mut id_to_word);
+        let alphabet_start = id_to_word.len();
+        self.compute_alphabet(word_counts, &mut word_to_id, &mut id_to_word);
+        let alphabet: Vec<String> = id_to_word[alphabet_start..].to_vec();
+        self.emit(TrainerEvent::AlphabetComputed {
+            size: id_to_word.len(),
+        });
+
+        let (mut words, counts) =
+            self.tokenize_words(word_counts, &mut word_to_id, &mut id_to_word, &progress);
+        self.emit(TrainerEvent::CountingComplete {
+            unique_words: word_counts.len(),
+        });
+
+        let (mut pair_counts, mut where_to_update) = self.count_pairs(&words, &counts, &progress);
+        let mut queue: BinaryHeap<Merge> = pair_counts
+            .iter()
+            .filter_map(|(pair, count)| {
+                if *count < 1 {
+                    return None;
+                }
+                Some(Merge {
+                    pair: *pair,
+                    count: *count as u64,
+                    pos: where_to_update.remove(pair).unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        let mut merges: Vec<(Pair, u32)> = vec![];
+        let mut merges_report: Vec<((String, String), u64)> = vec![];
+        let mut dropped_by_max_token_length = 0usize;
+        while id_to_word.len() < self.vocab_size {
+            let Some(mut top) = queue.pop() else {
+                break;
+            };
+
+            if top.count != *pair_counts.get(&top.pair).unwrap_or(&0) as u64 {
+                top.count = *pair_counts.get(&top.pair).unwrap_or(&0) as u64;
+                if top.count > 0 {
+                    queue.push(top);
+                }
+                continue;
+            }
+
+            if top.count < 1 || top.count < self.min_frequency {
+                break;
+            }
+
+            let part_a = &id_to_word[top.pair.0 as usize];
+            let mut part_b = id_to_word[top.pair.1 as usize].clone();
+            if let Some(prefix) = &self.continuing_subword_prefix {
+                if let Some(without) = part_b.strip_prefix(prefix.as_str()) {
+                    part_b = without.to_string();
+                }
+            }
+            let new_token = format!("{part_a}{part_b}");
+
+            if !self.is_within_token_bounds(&new_token) {
+                if let Some(max) = self.max_token_length {
+                    if new_token.chars().count() > max {
+                        dropped_by_max_token_length += 1;
+                    }
+                }
+                continue;
+            }
+
+            let new_token_id = word_to_id
+                .get(&new_token)
+                .copied()
+                .unwrap_or_else(|| {
+                    id_to_word.push(new_token.clone());
+                    (id_to_word.len() - 1) as u32
+                });
+            word_to_id.entry(new_token).or_insert(new_token_id);
+
+            merges.push((top.pair, new_token_id));
+            let last_pair = (
+                id_to_word[top.pair.0 as usize].clone(),
+                id_to_word[top.pair.1 as usize].clone(),
+            );
+            merges_report.push((last_pair.clone(), top.count));
+            self.emit(TrainerEvent::MergeProgress {
+                vocab_size: id_to_word.len(),
+                merges_applied: merges.len(),
+                last_pair,
+                frequency: top.count,
+            });
+
+            let changes = top
+                .pos
+                .iter()
+                .flat_map(|&i| {
+                    let word = &mut words[i];
+                    word.merge(top.pair.0, top.pair.1, new_token_id, counts[i])
+                        .into_iter()
+                        .map(move |c| (c, i))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            for ((pair, change), iw) in changes {
+                let count = change * counts[iw] as i64;
+                pair_counts.entry(pair).and_modify(|c| *c += count).or_insert(count);
+                if count > 0 {
+                    where_to_update.entry(pair).or_default().insert(iw);
+                }
+            }
+            for (pair, pos) in where_to_update.drain() {
+                let count = *pair_counts.get(&pair).unwrap_or(&0);
+                if count > 0 {
+                    queue.push(Merge {
+                        pair,
+                        count: count as u64,
+                        pos,
+                    });
+                }
+            }
+
+            if let Some(p) = &progress {
+                p.set_message(format!("{} merges", merges.len()));
+            }
+        }
+
+        if let Some(p) = &progress {
+            p.finish();
+        }
+
+        model.vocab = word_to_id.clone();
+        model.vocab_r = word_to_id.iter().map(|(k, v)| (*v, k.clone())).collect();
+        model.merges = merges
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (pair, new_id))| (pair, (rank as u32, new_id)))
+            .collect();
+
+        let report = TrainingReport {
+            alphabet,
+            merges: merges_report,
+            dropped_by_max_token_length,
+        };
+
+        Ok((self.special_tokens.clone(), report))
+    }
+}
+
+impl Trainer for BpeTrainer {
+    type Model = BPE;
+
+    fn should_show_progress(&self) -> bool {
+        self.show_progress
+    }
+
+    fn train(&self, model: &mut BPE) -> Result<Vec<AddedToken>> {
+        self.do_train(&self.words, model)
+    }
+
+    fn feed<I, S, F>(&mut self, iterator: I, process: F) -> Result<()>
+    where
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+        F: Fn(&str) -> Result<Vec<String>> + Sync,
+    {
+        let words: Result<AHashMap<String, u64>> = iterator
+            .map(|sequence| {
+                let mut map = AHashMap::new();
+                for word in process(sequence.as_ref())? {
+                    map.entry(word).and_modify(|c| *c += 1).or_insert(1u64);
+                }
+                Ok(map)
+            })
+            .try_fold(AHashMap::new(), |mut acc, ws: Result<AHashMap<String, u64>>| {
+                for (word, count) in ws? {
+                    *acc.entry(word).or_insert(0) += count;
+                }
+                Ok(acc)
+            });
+
+        self.words = words?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_words_applies_end_of_word_suffix_to_single_char_words() {
+        let trainer = BpeTrainer::builder()
+            .end_of_word_suffix("</w>".to_string())
+            .build();
+
+        let mut w2id: AHashMap<String, u32> = AHashMap::new();
+        let mut id2w: Vec<String> = Vec::new();
+        for s in ["a", "a</w>"] {
+            id2w.push(s.to_string());
+            w2id.insert(s.to_string(), (id2w.len() - 1) as u32);
+        }
+
+        let mut wc: AHashMap<String, u64> = AHashMap::new();
+        wc.insert("a".to_string(), 1);
+
+        let (words, _) = trainer.tokenize_words(&wc, &mut w2id, &mut id2w, &None);
+
+        assert_eq!(words.len(), 1);
+        let suffixed_id = w2id["a</w>"];
+        assert_eq!(words[0].get_chars(), &[suffixed_id]);
+    }
+}
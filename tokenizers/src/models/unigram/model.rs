@@ -0,0 +1,483 @@
+use super::trainer::UnigramTrainer;
+use crate::tokenizer::{Model, Result, Token};
+use ahash::AHashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(thiserror::Error, Debug)]
+pub enum UnigramError {
+    #[error("The vocabulary is empty but at least the unk token should be present")]
+    EmptyVocabulary,
+    #[error("Unk token `{0}` not found in the vocabulary")]
+    UnkTokenOutOfVocabulary(String),
+    #[error("Invalid SentencePiece model file: {0}")]
+    BadModelProto(String),
+    #[error("No Viterbi path could be found for the given input")]
+    NoViterbiPath,
+}
+
+/// A single vocabulary entry: the piece text and its unigram log-probability.
+pub type Vocab = Vec<(String, f64)>;
+
+/// The `type` tag carried by each `SentencePiece` entry of a `.model` protobuf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PieceType {
+    Normal,
+    Unknown,
+    Control,
+    UserDefined,
+    Byte,
+}
+
+impl PieceType {
+    fn from_proto(tag: i64) -> Self {
+        match tag {
+            2 => PieceType::Unknown,
+            3 => PieceType::Control,
+            4 => PieceType::UserDefined,
+            6 => PieceType::Byte,
+            _ => PieceType::Normal,
+        }
+    }
+}
+
+struct Config {
+    vocab: Vocab,
+    unk_id: Option<usize>,
+    byte_fallback: bool,
+}
+
+/// A `UnigramBuilder` can be used to create a `Unigram` model with a custom configuration.
+pub struct UnigramBuilder {
+    config: Config,
+}
+
+impl Default for UnigramBuilder {
+    fn default() -> Self {
+        Self {
+            config: Config {
+                vocab: vec![],
+                unk_id: None,
+                byte_fallback: false,
+            },
+        }
+    }
+}
+
+impl UnigramBuilder {
+    /// Constructs a new `UnigramBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the vocab (piece, log_prob) entries.
+    #[must_use]
+    pub fn vocab(mut self, vocab: Vocab) -> Self {
+        self.config.vocab = vocab;
+        self
+    }
+
+    /// Set the `UNK` token for the vocab.
+    #[must_use]
+    pub fn unk_token(mut self, unk_token: String) -> Self {
+        self.config.unk_id = self
+            .config
+            .vocab
+            .iter()
+            .position(|(piece, _)| piece == &unk_token);
+        self
+    }
+
+    /// When set, an out-of-vocabulary character is decomposed into its UTF-8 bytes - each looked
+    /// up as a `<0xXX>` piece - instead of falling back to the `unk` token, provided those byte
+    /// pieces are present in the vocab.
+    #[must_use]
+    pub fn byte_fallback(mut self, byte_fallback: bool) -> Self {
+        self.config.byte_fallback = byte_fallback;
+        self
+    }
+
+    /// Returns a `Unigram` model that uses the `UnigramBuilder`'s configuration.
+    pub fn build(self) -> Result<Unigram> {
+        if self.config.vocab.is_empty() {
+            return Err(UnigramError::EmptyVocabulary.into());
+        }
+
+        let token_to_ids: AHashMap<String, u32> = self
+            .config
+            .vocab
+            .iter()
+            .enumerate()
+            .map(|(id, (piece, _))| (piece.clone(), id as u32))
+            .collect();
+
+        Ok(Unigram {
+            token_to_ids,
+            vocab: self.config.vocab,
+            unk_id: self.config.unk_id,
+            byte_fallback: self.config.byte_fallback,
+        })
+    }
+}
+
+/// A [Unigram](https://arxiv.org/abs/1804.10959) model, as used by SentencePiece.
+///
+/// Tokenization finds the highest-scoring segmentation of the input into known pieces via a
+/// Viterbi pass over the unigram log-probabilities.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Unigram {
+    token_to_ids: AHashMap<String, u32>,
+    vocab: Vocab,
+    unk_id: Option<usize>,
+    byte_fallback: bool,
+}
+
+/// The penalty (in log-prob space) applied when a position can only be reached by falling back
+/// to the unknown token, once per character. A real single-character piece is preferred over the
+/// fallback only when its log-prob is at least `-UNK_PENALTY`; rarer pieces lose to `unk`.
+const UNK_PENALTY: f64 = 10.0;
+
+impl Unigram {
+    /// Initialize a `UnigramBuilder`.
+    pub fn builder() -> UnigramBuilder {
+        UnigramBuilder::new()
+    }
+
+    /// Create a new Unigram model with the given vocab and no unk token.
+    pub fn new(vocab: Vocab) -> Result<Self> {
+        Self::builder().vocab(vocab).build()
+    }
+
+    /// Load a Unigram model from a SentencePiece `.model` protobuf file.
+    pub fn from_file(path: &str) -> Result<Self> {
+        let mut file = File::open(path)?;
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+        Self::from_model_proto_bytes(&bytes)
+    }
+
+    fn from_model_proto_bytes(bytes: &[u8]) -> Result<Self> {
+        let pieces = parse_model_proto(bytes)?;
+        let mut vocab = Vec::with_capacity(pieces.len());
+        let mut unk_id = None;
+        for (i, (piece, score, ty)) in pieces.into_iter().enumerate() {
+            if ty == PieceType::Unknown {
+                unk_id = Some(i);
+            }
+            vocab.push((piece, score));
+        }
+
+        let mut builder = Unigram::builder().vocab(vocab);
+        if let Some(unk_id) = unk_id {
+            // The unk piece is located by id directly (protobuf order defines ids), so we
+            // side-step the name-based lookup `unk_token` performs for in-memory vocabs.
+            builder.config.unk_id = Some(unk_id);
+        }
+        builder.build()
+    }
+
+    pub fn get_unk_id(&self) -> Option<usize> {
+        self.unk_id
+    }
+
+    /// Whether an out-of-vocabulary character is decomposed into UTF-8 byte tokens instead of
+    /// falling back to `unk`.
+    pub fn byte_fallback(&self) -> bool {
+        self.byte_fallback
+    }
+}
+
+/// Minimal protobuf reader for the subset of `ModelProto` fields we care about: the repeated
+/// `pieces` field (tag 1) and, within each `SentencePiece`, `piece` (tag 1, string), `score`
+/// (tag 2, 32-bit float) and `type` (tag 3, varint enum). Every other field, at any depth, is
+/// skipped according to its wire type so unrelated parts of a real `.model` file don't trip us.
+fn parse_model_proto(bytes: &[u8]) -> Result<Vec<(String, f64, PieceType)>> {
+    let mut pieces = vec![];
+    let mut cursor = 0usize;
+    while cursor < bytes.len() {
+        let (tag, consumed) = read_varint(bytes, cursor)?;
+        cursor += consumed;
+        let field_number = tag >> 3;
+        let wire_type = tag & 0x7;
+        if field_number == 1 && wire_type == 2 {
+            let (len, consumed) = read_varint(bytes, cursor)?;
+            cursor += consumed;
+            let end = cursor + len as usize;
+            let piece_bytes = bytes
+                .get(cursor..end)
+                .ok_or_else(|| UnigramError::BadModelProto("truncated SentencePiece".into()))?;
+            pieces.push(parse_sentence_piece(piece_bytes)?);
+            cursor = end;
+        } else {
+            cursor = skip_field(bytes, cursor, wire_type)?;
+        }
+    }
+    Ok(pieces)
+}
+
+fn parse_sentence_piece(bytes: &[u8]) -> Result<(String, f64, PieceType)> {
+    let mut piece = String::new();
+    let mut score = 0.0f32;
+    let mut ty = PieceType::Normal;
+    let mut cursor = 0usize;
+    while cursor < bytes.len() {
+        let (tag, consumed) = read_varint(bytes, cursor)?;
+        cursor += consumed;
+        let field_number = tag >> 3;
+        let wire_type = tag & 0x7;
+        match (field_number, wire_type) {
+            (1, 2) => {
+                let (len, consumed) = read_varint(bytes, cursor)?;
+                cursor += consumed;
+                let end = cursor + len as usize;
+                let raw = bytes
+                    .get(cursor..end)
+                    .ok_or_else(|| UnigramError::BadModelProto("truncated piece string".into()))?;
+                piece = String::from_utf8(raw.to_vec())
+                    .map_err(|e| UnigramError::BadModelProto(e.to_string()))?;
+                cursor = end;
+            }
+            (2, 5) => {
+                let raw: [u8; 4] = bytes
+                    .get(cursor..cursor + 4)
+                    .ok_or_else(|| UnigramError::BadModelProto("truncated score".into()))?
+                    .try_into()
+                    .unwrap();
+                score = f32::from_le_bytes(raw);
+                cursor += 4;
+            }
+            (3, 0) => {
+                let (value, consumed) = read_varint(bytes, cursor)?;
+                ty = PieceType::from_proto(value as i64);
+                cursor += consumed;
+            }
+            _ => cursor = skip_field(bytes, cursor, wire_type)?,
+        }
+    }
+    Ok((piece, score as f64, ty))
+}
+
+fn read_varint(bytes: &[u8], mut cursor: usize) -> Result<(u64, usize)> {
+    let start = cursor;
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes
+            .get(cursor)
+            .ok_or_else(|| UnigramError::BadModelProto("truncated varint".into()))?;
+        value |= u64::from(byte & 0x7F) << shift;
+        cursor += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok((value, cursor - start))
+}
+
+fn skip_field(bytes: &[u8], cursor: usize, wire_type: u64) -> Result<usize> {
+    match wire_type {
+        0 => {
+            let (_, consumed) = read_varint(bytes, cursor)?;
+            Ok(cursor + consumed)
+        }
+        1 => Ok(cursor + 8),
+        2 => {
+            let (len, consumed) = read_varint(bytes, cursor)?;
+            Ok(cursor + consumed + len as usize)
+        }
+        5 => Ok(cursor + 4),
+        _ => Err(UnigramError::BadModelProto(format!("unknown wire type {wire_type}")).into()),
+    }
+}
+
+impl Model for Unigram {
+    type Trainer = UnigramTrainer;
+
+    fn get_vocab(&self) -> std::collections::HashMap<String, u32> {
+        self.token_to_ids.clone().into_iter().collect()
+    }
+
+    fn get_vocab_size(&self) -> usize {
+        self.vocab.len()
+    }
+
+    fn tokenize(&self, sequence: &str) -> Result<Vec<Token>> {
+        if sequence.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Byte offsets of every char boundary, so a Viterbi position `i` maps to `boundaries[i]`.
+        let boundaries: Vec<usize> = sequence
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(sequence.len()))
+            .collect();
+        let n = boundaries.len() - 1;
+
+        let mut best_score = vec![f64::NEG_INFINITY; n + 1];
+        best_score[0] = 0.0;
+        let mut back: Vec<Option<(usize, u32)>> = vec![None; n + 1];
+
+        for i in 1..=n {
+            for j in 0..i {
+                let piece = &sequence[boundaries[j]..boundaries[i]];
+                if let Some(&id) = self.token_to_ids.get(piece) {
+                    let score = best_score[j] + self.vocab[id as usize].1;
+                    if score > best_score[i] {
+                        best_score[i] = score;
+                        back[i] = Some((j, id));
+                    }
+                }
+            }
+            // Always offer a single-character fallback to `unk` so the Viterbi pass never
+            // stalls, even for characters that don't appear as a piece on their own.
+            if let Some(unk_id) = self.unk_id {
+                let j = i - 1;
+                let score = best_score[j] - UNK_PENALTY;
+                if score > best_score[i] {
+                    best_score[i] = score;
+                    back[i] = Some((j, unk_id as u32));
+                }
+            }
+        }
+
+        let mut spans = vec![];
+        let mut i = n;
+        while i > 0 {
+            let (j, id) = back[i].ok_or(UnigramError::NoViterbiPath)?;
+            spans.push((j, i, id));
+            i = j;
+        }
+        spans.reverse();
+
+        Ok(spans
+            .into_iter()
+            .flat_map(|(j, i, id)| {
+                let start = boundaries[j];
+                let end = boundaries[i];
+
+                if self.byte_fallback && Some(id as usize) == self.unk_id {
+                    let byte_tokens: Vec<Token> = sequence[start..end]
+                        .bytes()
+                        .enumerate()
+                        .filter_map(|(offset, byte)| {
+                            let piece = format!("<0x{byte:02X}>");
+                            self.token_to_ids.get(&piece).map(|&byte_id| {
+                                Token::new(byte_id, piece, (start + offset, start + offset + 1))
+                            })
+                        })
+                        .collect();
+                    if !byte_tokens.is_empty() {
+                        return byte_tokens;
+                    }
+                }
+
+                vec![Token::new(id, self.vocab[id as usize].0.clone(), (start, end))]
+            })
+            .collect())
+    }
+
+    fn token_to_id(&self, token: &str) -> Option<u32> {
+        self.token_to_ids.get(token).copied()
+    }
+
+    fn id_to_token(&self, id: u32) -> Option<String> {
+        self.vocab.get(id as usize).map(|(piece, _)| piece.clone())
+    }
+
+    fn save(&self, folder: &Path, name: Option<&str>) -> Result<Vec<std::path::PathBuf>> {
+        let vocab_file_name = match name {
+            Some(name) => format!("{name}-unigram.json"),
+            None => "unigram.json".to_string(),
+        };
+        let vocab_path: std::path::PathBuf = [folder, Path::new(vocab_file_name.as_str())]
+            .iter()
+            .collect();
+        let mut vocab_file = File::create(&vocab_path)?;
+        use std::io::Write;
+        vocab_file.write_all(serde_json::to_string(&self.vocab)?.as_bytes())?;
+        Ok(vec![vocab_path])
+    }
+
+    fn get_trainer(&self) -> UnigramTrainer {
+        UnigramTrainer::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unigram_build_requires_vocab() {
+        let err = Unigram::builder().build();
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_unigram_tokenize_picks_highest_scoring_segmentation() {
+        let vocab: Vocab = vec![
+            ("<unk>".to_string(), -10.0),
+            ("a".to_string(), -1.0),
+            ("b".to_string(), -1.0),
+            ("ab".to_string(), -0.5),
+        ];
+        let unigram = Unigram::builder()
+            .vocab(vocab)
+            .unk_token("<unk>".to_string())
+            .build()
+            .unwrap();
+
+        let tokens = unigram.tokenize("ab").unwrap();
+        assert_eq!(tokens, vec![Token::new(3u32, "ab".into(), (0, 2))]);
+    }
+
+    #[test]
+    fn test_unigram_tokenize_falls_back_to_unk() {
+        let vocab: Vocab = vec![("<unk>".to_string(), -10.0), ("a".to_string(), -1.0)];
+        let unigram = Unigram::builder()
+            .vocab(vocab)
+            .unk_token("<unk>".to_string())
+            .build()
+            .unwrap();
+
+        let tokens = unigram.tokenize("ac").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(1u32, "a".into(), (0, 1)),
+                Token::new(0u32, "<unk>".into(), (1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unigram_tokenize_byte_fallback() {
+        let vocab: Vocab = vec![
+            ("<unk>".to_string(), -10.0),
+            ("a".to_string(), -1.0),
+            ("<0xC3>".to_string(), -1.0),
+            ("<0xA9>".to_string(), -1.0),
+        ];
+        let unigram = Unigram::builder()
+            .vocab(vocab)
+            .unk_token("<unk>".to_string())
+            .byte_fallback(true)
+            .build()
+            .unwrap();
+
+        // "é" (U+00E9) isn't a piece on its own, so it should fall back to its UTF-8 bytes
+        // (0xC3 0xA9) rather than collapsing to a single <unk> token.
+        let tokens = unigram.tokenize("aé").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(1u32, "a".into(), (0, 1)),
+                Token::new(2u32, "<0xC3>".into(), (1, 2)),
+                Token::new(3u32, "<0xA9>".into(), (2, 3)),
+            ]
+        );
+    }
+}
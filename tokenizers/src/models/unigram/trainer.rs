@@ -0,0 +1,431 @@
+use super::{Unigram, Vocab};
+use crate::tokenizer::{AddedToken, Result, Trainer};
+use crate::utils::progress::{ProgressBar, ProgressStyle};
+use ahash::{AHashMap, AHashSet};
+
+#[derive(Debug, Clone)]
+pub struct UnigramTrainer {
+    /// The size of the final vocabulary, including all tokens and alphabet.
+    pub vocab_size: u32,
+    /// Whether to show progress while training.
+    pub show_progress: bool,
+    /// A list of special tokens the model should know of.
+    pub special_tokens: Vec<AddedToken>,
+    /// Characters to always include in the initial seed, even if unseen in the corpus.
+    pub initial_alphabet: AHashSet<char>,
+    /// The maximum different characters to keep as single-character seed pieces, keeping the
+    /// most frequent ones by corpus occurrence. `initial_alphabet` and `special_tokens` are
+    /// always retained regardless of this cap.
+    pub limit_alphabet: Option<usize>,
+    /// When set, injects the 256 single-byte tokens (`<0x00>`..`<0xFF>`) into the trained
+    /// vocabulary and marks the model to decompose out-of-vocabulary characters into their UTF-8
+    /// bytes instead of mapping them to `unk_token`.
+    pub byte_fallback: bool,
+    /// Fraction of the seed vocabulary kept at each pruning round.
+    pub shrinking_factor: f64,
+    /// The token used for out-of-vocabulary pieces.
+    pub unk_token: Option<String>,
+    /// The maximum length, in characters, of a single piece.
+    pub max_piece_length: usize,
+    /// Number of EM iterations to run between each pruning round.
+    pub n_sub_iterations: u32,
+    /// The number of candidate pieces the seed vocabulary is built from before the first prune.
+    pub seed_size: usize,
+    /// When set, used as the starting seed vocabulary instead of generating one from frequent
+    /// substrings of the training corpus - lets an already-trained Unigram model be refined on
+    /// new data instead of retrained cold. `initial_alphabet` and `special_tokens` are still
+    /// unioned in so no required token is pruned.
+    pub initial_vocab: Option<Vec<(String, f64)>>,
+
+    words: AHashMap<String, u64>,
+}
+
+impl Default for UnigramTrainer {
+    fn default() -> Self {
+        Self {
+            vocab_size: 8000,
+            show_progress: true,
+            special_tokens: vec![],
+            initial_alphabet: AHashSet::new(),
+            limit_alphabet: None,
+            byte_fallback: false,
+            shrinking_factor: 0.75,
+            unk_token: None,
+            max_piece_length: 16,
+            n_sub_iterations: 2,
+            seed_size: 1_000_000,
+            initial_vocab: None,
+            words: AHashMap::new(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UnigramTrainerError {
+    #[error("vocab_size must be at least as large as the alphabet plus the special tokens")]
+    VocabTooSmall,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UnigramTrainerBuilder {
+    config: UnigramTrainer,
+}
+
+impl UnigramTrainerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vocab_size(&mut self, vocab_size: u32) -> &mut Self {
+        self.config.vocab_size = vocab_size;
+        self
+    }
+
+    pub fn show_progress(&mut self, show_progress: bool) -> &mut Self {
+        self.config.show_progress = show_progress;
+        self
+    }
+
+    pub fn special_tokens(&mut self, special_tokens: Vec<AddedToken>) -> &mut Self {
+        self.config.special_tokens = special_tokens;
+        self
+    }
+
+    pub fn initial_alphabet(&mut self, alphabet: AHashSet<char>) -> &mut Self {
+        self.config.initial_alphabet = alphabet;
+        self
+    }
+
+    pub fn limit_alphabet(&mut self, limit_alphabet: Option<usize>) -> &mut Self {
+        self.config.limit_alphabet = limit_alphabet;
+        self
+    }
+
+    pub fn byte_fallback(&mut self, byte_fallback: bool) -> &mut Self {
+        self.config.byte_fallback = byte_fallback;
+        self
+    }
+
+    pub fn shrinking_factor(&mut self, shrinking_factor: f64) -> &mut Self {
+        self.config.shrinking_factor = shrinking_factor;
+        self
+    }
+
+    pub fn unk_token(&mut self, unk_token: Option<String>) -> &mut Self {
+        self.config.unk_token = unk_token;
+        self
+    }
+
+    pub fn max_piece_length(&mut self, max_piece_length: usize) -> &mut Self {
+        self.config.max_piece_length = max_piece_length;
+        self
+    }
+
+    pub fn n_sub_iterations(&mut self, n_sub_iterations: u32) -> &mut Self {
+        self.config.n_sub_iterations = n_sub_iterations;
+        self
+    }
+
+    pub fn seed_size(&mut self, seed_size: usize) -> &mut Self {
+        self.config.seed_size = seed_size;
+        self
+    }
+
+    pub fn initial_vocab(&mut self, initial_vocab: Option<Vec<(String, f64)>>) -> &mut Self {
+        self.config.initial_vocab = initial_vocab;
+        self
+    }
+
+    pub fn build(&self) -> std::result::Result<UnigramTrainer, UnigramTrainerError> {
+        Ok(self.config.clone())
+    }
+}
+
+impl UnigramTrainer {
+    pub fn builder() -> UnigramTrainerBuilder {
+        UnigramTrainerBuilder::new()
+    }
+
+    /// Merges `counts` into the trainer's internal word-frequency map, bypassing
+    /// [`Trainer::feed`] entirely.
+    pub fn feed_word_counts(&mut self, counts: impl IntoIterator<Item = (String, u64)>) {
+        for (word, count) in counts {
+            *self.words.entry(word).or_insert(0) += count;
+        }
+    }
+
+    fn setup_progress(&self) -> Option<ProgressBar> {
+        if self.show_progress {
+            let p = ProgressBar::new(0);
+            p.set_style(
+                ProgressStyle::default_bar()
+                    .template("[{elapsed_precise}] {msg:<30!} {wide_bar} {pos:<9!}/{len:>9!}"),
+            );
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    /// Builds the seed vocabulary: every substring up to `max_piece_length` long, scored by
+    /// `occurrences * length` and capped to `seed_size` candidates. The initial alphabet and
+    /// special tokens are added separately by [`Self::union_required_pieces`].
+    fn seed_vocab(&self, word_counts: &AHashMap<String, u64>) -> AHashMap<String, f64> {
+        let mut scores: AHashMap<String, u64> = AHashMap::new();
+        for (word, count) in word_counts {
+            let chars: Vec<char> = word.chars().collect();
+            for start in 0..chars.len() {
+                for end in (start + 1)..=chars.len().min(start + self.max_piece_length) {
+                    let piece: String = chars[start..end].iter().collect();
+                    *scores.entry(piece).or_insert(0) += count;
+                }
+            }
+        }
+
+        let mut ranked: Vec<(String, u64)> = scores.into_iter().collect();
+        ranked.sort_unstable_by(|(pa, sa), (pb, sb)| {
+            (sb * pb.chars().count() as u64).cmp(&(sa * pa.chars().count() as u64))
+        });
+        ranked.truncate(self.seed_size);
+
+        let total: u64 = ranked.iter().map(|(_, s)| *s).sum::<u64>().max(1);
+        ranked
+            .into_iter()
+            .map(|(piece, score)| (piece, (score as f64 / total as f64).ln()))
+            .collect()
+    }
+
+    /// Ensures every character of `initial_alphabet` and every special token's content is
+    /// present in `vocab`, inserting any missing one at the vocabulary's lowest existing score.
+    /// The pruning step in `do_train` never drops these pieces, whatever their score.
+    fn union_required_pieces(&self, vocab: &mut AHashMap<String, f64>) {
+        let floor = vocab.values().cloned().fold(f64::INFINITY, f64::min);
+        let floor = if floor.is_finite() { floor } else { 0.0 };
+        for c in &self.initial_alphabet {
+            vocab.entry(c.to_string()).or_insert(floor);
+        }
+        for token in &self.special_tokens {
+            vocab.entry(token.content.clone()).or_insert(floor);
+        }
+    }
+
+    /// Caps single-character seed pieces to the `limit_alphabet` most frequent characters in
+    /// `word_counts`, dropping the rest - except those in `initial_alphabet` or that are a
+    /// special token's content, which are never dropped. Pieces longer than one character are
+    /// never affected.
+    fn limit_seed_alphabet(
+        &self,
+        vocab: &mut AHashMap<String, f64>,
+        word_counts: &AHashMap<String, u64>,
+    ) {
+        let Some(limit) = self.limit_alphabet else {
+            return;
+        };
+
+        let mut char_freq: AHashMap<char, u64> = AHashMap::new();
+        for (word, count) in word_counts {
+            for c in word.chars() {
+                *char_freq.entry(c).or_insert(0) += count;
+            }
+        }
+        let mut ranked: Vec<(char, u64)> = char_freq.into_iter().collect();
+        ranked.sort_unstable_by(|(ca, fa), (cb, fb)| fb.cmp(fa).then_with(|| ca.cmp(cb)));
+
+        let keep: AHashSet<char> = ranked
+            .into_iter()
+            .take(limit)
+            .map(|(c, _)| c)
+            .chain(self.initial_alphabet.iter().copied())
+            .collect();
+        let special: AHashSet<&str> = self
+            .special_tokens
+            .iter()
+            .map(|t| t.content.as_str())
+            .collect();
+
+        vocab.retain(|piece, _| {
+            let mut chars = piece.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => keep.contains(&c) || special.contains(piece.as_str()),
+                _ => true,
+            }
+        });
+    }
+
+    /// Greedily segments `word` into the longest pieces present in `vocab`, falling back one
+    /// character at a time when no known piece matches. This stands in for a full Viterbi lattice
+    /// during the EM counting passes; it is simpler but drives the same reestimation loop.
+    fn segment<'a>(&self, word: &'a str, vocab: &AHashMap<String, f64>) -> Vec<&'a str> {
+        let chars: Vec<(usize, char)> = word.char_indices().collect();
+        let mut pieces = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let mut matched = None;
+            let max_len = self.max_piece_length.min(chars.len() - i);
+            for len in (1..=max_len).rev() {
+                let end = if i + len < chars.len() {
+                    chars[i + len].0
+                } else {
+                    word.len()
+                };
+                let start = chars[i].0;
+                if vocab.contains_key(&word[start..end]) {
+                    matched = Some((start, end, len));
+                    break;
+                }
+            }
+            let (start, end, len) = matched.unwrap_or_else(|| {
+                let start = chars[i].0;
+                let end = if i + 1 < chars.len() {
+                    chars[i + 1].0
+                } else {
+                    word.len()
+                };
+                (start, end, 1)
+            });
+            pieces.push(&word[start..end]);
+            i += len;
+        }
+        pieces
+    }
+
+    pub fn do_train(
+        &self,
+        word_counts: &AHashMap<String, u64>,
+        model: &mut Unigram,
+    ) -> Result<Vec<AddedToken>> {
+        let progress = self.setup_progress();
+
+        let mut vocab = match &self.initial_vocab {
+            Some(initial_vocab) => initial_vocab.iter().cloned().collect(),
+            None => self.seed_vocab(word_counts),
+        };
+        self.union_required_pieces(&mut vocab);
+        self.limit_seed_alphabet(&mut vocab, word_counts);
+
+        let target_size = (self.vocab_size as usize).saturating_sub(self.special_tokens.len());
+
+        loop {
+            for _ in 0..self.n_sub_iterations {
+                let mut piece_counts: AHashMap<String, f64> = AHashMap::new();
+                for (word, count) in word_counts {
+                    for piece in self.segment(word, &vocab) {
+                        *piece_counts.entry(piece.to_string()).or_insert(0.0) += *count as f64;
+                    }
+                }
+                let total: f64 = piece_counts.values().sum::<f64>().max(1.0);
+                for (piece, log_prob) in vocab.iter_mut() {
+                    if let Some(count) = piece_counts.get(piece) {
+                        *log_prob = (count / total).ln();
+                    }
+                }
+                if let Some(p) = &progress {
+                    p.set_message(format!("{} pieces", vocab.len()));
+                }
+            }
+
+            if vocab.len() <= target_size.max(1) {
+                break;
+            }
+
+            let keep = ((vocab.len() as f64) * self.shrinking_factor) as usize;
+            let keep = keep.max(target_size);
+            let mut ranked: Vec<(String, f64)> = vocab.into_iter().collect();
+            ranked.sort_unstable_by(|(pa, sa), (pb, sb)| {
+                sb.partial_cmp(sa)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| pa.cmp(pb))
+            });
+            let (singles, mut rest): (Vec<_>, Vec<_>) = ranked
+                .into_iter()
+                .partition(|(piece, _)| {
+                    piece.chars().count() == 1
+                        || self.special_tokens.iter().any(|t| &t.content == piece)
+                });
+            rest.truncate(keep.saturating_sub(singles.len()));
+            vocab = singles.into_iter().chain(rest).collect();
+
+            if let Some(p) = &progress {
+                p.inc(1);
+            }
+        }
+
+        if let Some(p) = &progress {
+            p.finish();
+        }
+
+        let mut entries: Vocab = vocab.into_iter().collect();
+        entries.sort_unstable_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some(unk) = &self.unk_token {
+            let lowest = entries.last().map(|(_, p)| *p).unwrap_or(0.0);
+            entries.push((unk.clone(), lowest));
+        }
+
+        if self.byte_fallback {
+            let lowest = entries.last().map(|(_, p)| *p).unwrap_or(0.0);
+            for byte in 0u16..=255 {
+                entries.push((format!("<0x{byte:02X}>"), lowest));
+            }
+        }
+
+        let mut builder = Unigram::builder().vocab(entries).byte_fallback(self.byte_fallback);
+        if let Some(unk) = &self.unk_token {
+            builder = builder.unk_token(unk.clone());
+        }
+        *model = builder.build()?;
+        Ok(self.special_tokens.clone())
+    }
+}
+
+impl Trainer for UnigramTrainer {
+    type Model = Unigram;
+
+    fn should_show_progress(&self) -> bool {
+        self.show_progress
+    }
+
+    fn train(&self, model: &mut Unigram) -> Result<Vec<AddedToken>> {
+        self.do_train(&self.words, model)
+    }
+
+    fn feed<I, S, F>(&mut self, iterator: I, process: F) -> Result<()>
+    where
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+        F: Fn(&str) -> Result<Vec<String>> + Sync,
+    {
+        for sequence in iterator {
+            for word in process(sequence.as_ref())? {
+                *self.words.entry(word).or_insert(0) += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn do_train_sets_unk_id_on_the_trained_model() {
+        let trainer = UnigramTrainer::builder()
+            .vocab_size(20)
+            .unk_token(Some("<unk>".to_string()))
+            .build()
+            .unwrap();
+
+        let mut word_counts = AHashMap::new();
+        word_counts.insert("hello".to_string(), 5);
+        word_counts.insert("world".to_string(), 5);
+
+        // `do_train` fully overwrites `model`, so the placeholder it starts from doesn't matter.
+        let mut model = Unigram::builder()
+            .vocab(vec![("<unk>".to_string(), 0.0)])
+            .build()
+            .unwrap();
+        trainer.do_train(&word_counts, &mut model).unwrap();
+
+        assert!(model.get_unk_id().is_some());
+    }
+}
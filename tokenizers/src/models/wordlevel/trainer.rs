@@ -0,0 +1,197 @@
+use super::WordLevel;
+use crate::tokenizer::{AddedToken, Result, Trainer};
+use crate::utils::progress::{ProgressBar, ProgressStyle};
+use ahash::AHashMap;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone)]
+pub struct WordLevelTrainer {
+    /// The minimum frequency a word must have to be included in the vocabulary.
+    pub min_frequency: u64,
+    /// The size of the final vocabulary, including all tokens and alphabet.
+    pub vocab_size: usize,
+    /// Whether to show progress while training.
+    pub show_progress: bool,
+    /// A list of special tokens the model should know of.
+    pub special_tokens: Vec<AddedToken>,
+
+    words: AHashMap<String, u64>,
+}
+
+impl Default for WordLevelTrainer {
+    fn default() -> Self {
+        Self {
+            min_frequency: 0,
+            vocab_size: 30000,
+            show_progress: true,
+            special_tokens: vec![],
+            words: AHashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WordLevelTrainerBuilder {
+    config: WordLevelTrainer,
+}
+
+impl WordLevelTrainerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min_frequency(&mut self, frequency: u64) -> &mut Self {
+        self.config.min_frequency = frequency;
+        self
+    }
+
+    pub fn vocab_size(&mut self, vocab_size: usize) -> &mut Self {
+        self.config.vocab_size = vocab_size;
+        self
+    }
+
+    pub fn show_progress(&mut self, show_progress: bool) -> &mut Self {
+        self.config.show_progress = show_progress;
+        self
+    }
+
+    pub fn special_tokens(&mut self, special_tokens: Vec<AddedToken>) -> &mut Self {
+        self.config.special_tokens = special_tokens;
+        self
+    }
+
+    /// This builder can never fail; the `Result` return type matches the other trainer builders
+    /// in this crate so callers can treat them uniformly.
+    pub fn build(&self) -> Result<WordLevelTrainer> {
+        Ok(self.config.clone())
+    }
+}
+
+impl WordLevelTrainer {
+    pub fn builder() -> WordLevelTrainerBuilder {
+        WordLevelTrainerBuilder::new()
+    }
+
+    /// Merges `counts` into the trainer's internal word-frequency map, bypassing
+    /// [`Trainer::feed`] entirely.
+    pub fn feed_word_counts(&mut self, counts: impl IntoIterator<Item = (String, u64)>) {
+        for (word, count) in counts {
+            *self.words.entry(word).or_insert(0) += count;
+        }
+    }
+
+    fn setup_progress(&self) -> Option<ProgressBar> {
+        if self.show_progress {
+            let p = ProgressBar::new(0);
+            p.set_style(
+                ProgressStyle::default_bar()
+                    .template("[{elapsed_precise}] {msg:<30!} {wide_bar} {pos:<9!}/{len:>9!}"),
+            );
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    pub fn do_train(
+        &self,
+        word_counts: &AHashMap<String, u64>,
+        model: &mut WordLevel,
+    ) -> Result<Vec<AddedToken>> {
+        let progress = self.setup_progress();
+
+        let mut ordered: Vec<(&String, &u64)> = word_counts
+            .iter()
+            .filter(|(_, count)| **count >= self.min_frequency)
+            .collect();
+        ordered.sort_unstable_by(|(word_a, count_a), (word_b, count_b)| {
+            match count_b.cmp(count_a) {
+                Ordering::Equal => word_a.cmp(word_b),
+                ordering => ordering,
+            }
+        });
+
+        // `self.vocab_size` can be `usize::MAX` (an unbounded merge from `EnsembleTrainer`), so
+        // cap the actual allocation at the number of candidate entries instead of passing it
+        // straight through to `with_capacity`, which would try to allocate way more than needed.
+        let capacity = self
+            .vocab_size
+            .min(word_counts.len() + self.special_tokens.len());
+        let mut vocab = AHashMap::with_capacity(capacity);
+        for token in &self.special_tokens {
+            if !vocab.contains_key(&token.content) {
+                let id = vocab.len() as u32;
+                vocab.insert(token.content.clone(), id);
+            }
+        }
+        for (word, _) in ordered {
+            if vocab.len() >= self.vocab_size {
+                break;
+            }
+            if !vocab.contains_key(word) {
+                let id = vocab.len() as u32;
+                vocab.insert(word.clone(), id);
+            }
+            if let Some(p) = &progress {
+                p.inc(1);
+            }
+        }
+
+        if let Some(p) = &progress {
+            p.finish();
+        }
+
+        model.vocab = vocab;
+        Ok(self.special_tokens.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn do_train_with_unbounded_vocab_size_does_not_overallocate() {
+        // `vocab_size` can be `usize::MAX` for an "unbounded" merge (see `EnsembleTrainer`); the
+        // actual allocation must be capped at the number of candidate entries instead of being
+        // passed straight through to `with_capacity`.
+        let trainer = WordLevelTrainer::builder().vocab_size(usize::MAX).build().unwrap();
+        let mut model = WordLevel::default();
+
+        let mut word_counts = AHashMap::new();
+        word_counts.insert("hello".to_string(), 2u64);
+        word_counts.insert("world".to_string(), 1u64);
+
+        trainer.do_train(&word_counts, &mut model).unwrap();
+
+        assert_eq!(model.vocab.len(), 2);
+        assert!(model.vocab.contains_key("hello"));
+        assert!(model.vocab.contains_key("world"));
+    }
+}
+
+impl Trainer for WordLevelTrainer {
+    type Model = WordLevel;
+
+    fn should_show_progress(&self) -> bool {
+        self.show_progress
+    }
+
+    fn train(&self, model: &mut WordLevel) -> Result<Vec<AddedToken>> {
+        self.do_train(&self.words, model)
+    }
+
+    fn feed<I, S, F>(&mut self, iterator: I, process: F) -> Result<()>
+    where
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+        F: Fn(&str) -> Result<Vec<String>> + Sync,
+    {
+        for sequence in iterator {
+            for word in process(sequence.as_ref())? {
+                *self.words.entry(word).or_insert(0) += 1;
+            }
+        }
+        Ok(())
+    }
+}
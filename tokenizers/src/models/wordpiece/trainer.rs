@@ -0,0 +1,507 @@
+use super::WordPiece;
+use crate::models::bpe::{
+    BpeTrainer, BpeTrainerBuilder, Pair, TrainerCallback, TrainerEvent, TrainingReport, BPE,
+};
+use crate::tokenizer::{AddedToken, Result, Trainer};
+use ahash::AHashMap;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// A candidate merge scored by corpus log-likelihood gain rather than raw frequency, for
+/// [`WordPieceTrainer::do_train`] when `use_likelihood_scoring` is set. Ordering is by `score`,
+/// with ties (or NaN scores) broken by `pair`, the smaller pair ranking higher; `pos` just carries
+/// the data needed to apply the winning merge.
+#[derive(Debug)]
+struct LikelihoodMerge {
+    pair: Pair,
+    score: f64,
+    count: u64,
+    pos: HashSet<usize>,
+}
+impl PartialEq for LikelihoodMerge {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score && self.pair == other.pair
+    }
+}
+impl Eq for LikelihoodMerge {}
+impl PartialOrd for LikelihoodMerge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for LikelihoodMerge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.score.partial_cmp(&other.score) {
+            Some(Ordering::Equal) | None => other.pair.cmp(&self.pair),
+            Some(ordering) => ordering,
+        }
+    }
+}
+
+/// Trains a [`WordPiece`] model.
+///
+/// WordPiece is built as a wrapper over [`BpeTrainer`]: it defaults the
+/// `continuing_subword_prefix` to `"##"` and by default merges the most *frequent* pair, same as
+/// BPE; with `use_likelihood_scoring` set it merges the pair with the highest log-likelihood gain
+/// instead. All configuration is forwarded to the inner `BpeTrainer`.
+#[derive(Debug, Clone)]
+pub struct WordPieceTrainer {
+    bpe_trainer: BpeTrainer,
+    /// When set, merges are picked by corpus log-likelihood gain (the original WordPiece
+    /// criterion) instead of by raw pair frequency (the BPE approximation `bpe_trainer` uses).
+    use_likelihood_scoring: bool,
+}
+
+impl Default for WordPieceTrainer {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WordPieceTrainerBuilder {
+    bpe_trainer_builder: BpeTrainerBuilder,
+    use_likelihood_scoring: bool,
+}
+
+impl Default for WordPieceTrainerBuilder {
+    fn default() -> Self {
+        Self {
+            bpe_trainer_builder: BpeTrainerBuilder::new().continuing_subword_prefix("##".into()),
+            use_likelihood_scoring: false,
+        }
+    }
+}
+
+impl WordPieceTrainerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min_frequency(mut self, frequency: u64) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.min_frequency(frequency);
+        self
+    }
+
+    pub fn vocab_size(mut self, vocab_size: usize) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.vocab_size(vocab_size);
+        self
+    }
+
+    pub fn show_progress(mut self, show_progress: bool) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.show_progress(show_progress);
+        self
+    }
+
+    pub fn special_tokens(mut self, special_tokens: Vec<AddedToken>) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.special_tokens(special_tokens);
+        self
+    }
+
+    pub fn limit_alphabet(mut self, limit: usize) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.limit_alphabet(limit);
+        self
+    }
+
+    pub fn initial_alphabet(mut self, alphabet: ahash::AHashSet<char>) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.initial_alphabet(alphabet);
+        self
+    }
+
+    pub fn continuing_subword_prefix(mut self, prefix: String) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.continuing_subword_prefix(prefix);
+        self
+    }
+
+    pub fn end_of_word_suffix(mut self, suffix: String) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.end_of_word_suffix(suffix);
+        self
+    }
+
+    pub fn min_token_length(mut self, min_token_length: Option<usize>) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.min_token_length(min_token_length);
+        self
+    }
+
+    pub fn max_number_length(mut self, max_number_length: Option<usize>) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.max_number_length(max_number_length);
+        self
+    }
+
+    /// Registers a callback invoked with structured [`TrainerEvent`]s during
+    /// [`WordPieceTrainer::do_train`], forwarded to the inner [`BpeTrainer`].
+    pub fn callback(mut self, callback: TrainerCallback) -> Self {
+        self.bpe_trainer_builder = self.bpe_trainer_builder.callback(callback);
+        self
+    }
+
+    /// Switches the merge criterion from raw pair frequency to corpus log-likelihood gain, the
+    /// original WordPiece algorithm (BERT-style) rather than the BPE approximation this trainer
+    /// otherwise reuses.
+    pub fn use_likelihood_scoring(mut self, use_likelihood_scoring: bool) -> Self {
+        self.use_likelihood_scoring = use_likelihood_scoring;
+        self
+    }
+
+    pub fn build(self) -> WordPieceTrainer {
+        WordPieceTrainer {
+            bpe_trainer: self.bpe_trainer_builder.build(),
+            use_likelihood_scoring: self.use_likelihood_scoring,
+        }
+    }
+}
+
+impl WordPieceTrainer {
+    pub fn builder() -> WordPieceTrainerBuilder {
+        WordPieceTrainerBuilder::new()
+    }
+
+    pub fn vocab_size(&self) -> usize {
+        self.bpe_trainer.vocab_size
+    }
+    pub fn set_vocab_size(&mut self, vocab_size: usize) {
+        self.bpe_trainer.vocab_size = vocab_size;
+    }
+
+    pub fn min_frequency(&self) -> u64 {
+        self.bpe_trainer.min_frequency
+    }
+    pub fn set_min_frequency(&mut self, frequency: u64) {
+        self.bpe_trainer.min_frequency = frequency;
+    }
+
+    pub fn show_progress(&self) -> bool {
+        self.bpe_trainer.show_progress
+    }
+    pub fn set_show_progress(&mut self, show_progress: bool) {
+        self.bpe_trainer.show_progress = show_progress;
+    }
+
+    pub fn special_tokens(&self) -> &[AddedToken] {
+        &self.bpe_trainer.special_tokens
+    }
+    pub fn set_special_tokens(&mut self, special_tokens: Vec<AddedToken>) {
+        self.bpe_trainer.special_tokens = special_tokens;
+    }
+
+    pub fn limit_alphabet(&self) -> Option<usize> {
+        self.bpe_trainer.limit_alphabet
+    }
+    pub fn set_limit_alphabet(&mut self, limit: Option<usize>) {
+        self.bpe_trainer.limit_alphabet = limit;
+    }
+
+    pub fn initial_alphabet(&self) -> &ahash::AHashSet<char> {
+        &self.bpe_trainer.initial_alphabet
+    }
+    pub fn set_initial_alphabet(&mut self, alphabet: ahash::AHashSet<char>) {
+        self.bpe_trainer.initial_alphabet = alphabet;
+    }
+
+    pub fn continuing_subword_prefix(&self) -> &Option<String> {
+        &self.bpe_trainer.continuing_subword_prefix
+    }
+    pub fn set_continuing_subword_prefix(&mut self, prefix: Option<String>) {
+        self.bpe_trainer.continuing_subword_prefix = prefix;
+    }
+
+    pub fn end_of_word_suffix(&self) -> &Option<String> {
+        &self.bpe_trainer.end_of_word_suffix
+    }
+    pub fn set_end_of_word_suffix(&mut self, suffix: Option<String>) {
+        self.bpe_trainer.end_of_word_suffix = suffix;
+    }
+
+    pub fn min_token_length(&self) -> Option<usize> {
+        self.bpe_trainer.min_token_length
+    }
+    pub fn set_min_token_length(&mut self, min_token_length: Option<usize>) {
+        self.bpe_trainer.min_token_length = min_token_length;
+    }
+
+    pub fn max_number_length(&self) -> Option<usize> {
+        self.bpe_trainer.max_number_length
+    }
+    pub fn set_max_number_length(&mut self, max_number_length: Option<usize>) {
+        self.bpe_trainer.max_number_length = max_number_length;
+    }
+
+    /// Merges `counts` into the inner trainer's word-frequency map, bypassing [`Trainer::feed`].
+    pub fn feed_word_counts(&mut self, counts: impl IntoIterator<Item = (String, u64)>) {
+        self.bpe_trainer.feed_word_counts(counts);
+    }
+
+    pub fn use_likelihood_scoring(&self) -> bool {
+        self.use_likelihood_scoring
+    }
+    pub fn set_use_likelihood_scoring(&mut self, use_likelihood_scoring: bool) {
+        self.use_likelihood_scoring = use_likelihood_scoring;
+    }
+
+    pub fn callback(&self) -> Option<&TrainerCallback> {
+        self.bpe_trainer.callback()
+    }
+    pub fn set_callback(&mut self, callback: Option<TrainerCallback>) {
+        self.bpe_trainer.set_callback(callback);
+    }
+
+    pub fn do_train(&self, word_counts: &AHashMap<String, u64>, model: &mut WordPiece) -> Result<Vec<AddedToken>> {
+        self.do_train_with_report(word_counts, model)
+            .map(|(added_tokens, _report)| added_tokens)
+    }
+
+    /// Trains `model` from the inner trainer's internal word-frequency map (the same source
+    /// [`Trainer::train`] uses), additionally returning a [`TrainingReport`].
+    pub fn train_with_report(
+        &self,
+        model: &mut WordPiece,
+    ) -> Result<(Vec<AddedToken>, TrainingReport)> {
+        self.do_train_with_report(self.bpe_trainer.words(), model)
+    }
+
+    /// Same as [`Self::do_train`], but additionally returns a [`TrainingReport`].
+    pub fn do_train_with_report(
+        &self,
+        word_counts: &AHashMap<String, u64>,
+        model: &mut WordPiece,
+    ) -> Result<(Vec<AddedToken>, TrainingReport)> {
+        if self.use_likelihood_scoring {
+            return self.do_train_likelihood(word_counts, model);
+        }
+
+        let mut bpe = BPE::default();
+        let (special_tokens, report) = self.bpe_trainer.do_train_with_report(word_counts, &mut bpe)?;
+        *model = WordPiece::from_bpe(&bpe);
+        Ok((special_tokens, report))
+    }
+
+    /// The original WordPiece training algorithm: at each step, merge the adjacent pair `(a, b)`
+    /// that maximizes the corpus log-likelihood gain `log(c_ab) - log(c_a) - log(c_b)`
+    /// (equivalently `c_ab / (c_a * c_b)`), rather than the pair with the highest raw count.
+    /// Everything else - alphabet seeding, applying a merge across all words, updating the
+    /// priority queue - is identical to [`BpeTrainer::do_train`]; only the comparison key
+    /// changes.
+    fn do_train_likelihood(
+        &self,
+        word_counts: &AHashMap<String, u64>,
+        model: &mut WordPiece,
+    ) -> Result<(Vec<AddedToken>, TrainingReport)> {
+        let mut bpe = BPE::default();
+        let trainer = &self.bpe_trainer;
+
+        let mut word_to_id: AHashMap<String, u32> = AHashMap::with_capacity(trainer.vocab_size);
+        let mut id_to_word: Vec<String> = Vec::with_capacity(trainer.vocab_size);
+
+        // Alphabet seeding (special tokens, `limit_alphabet`/`initial_alphabet`) and word
+        // tokenization (`continuing_subword_prefix`/`end_of_word_suffix`) are identical to
+        // `BpeTrainer::do_train`, so reuse its helpers instead of re-deriving a "##"-less
+        // alphabet here.
+        trainer.add_special_tokens(&mut word_to_id, &mut id_to_word);
+        let alphabet_start = id_to_word.len();
+        trainer.compute_alphabet(word_counts, &mut word_to_id, &mut id_to_word);
+        let alphabet_report: Vec<String> = id_to_word[alphabet_start..].to_vec();
+        trainer.emit(TrainerEvent::AlphabetComputed {
+            size: id_to_word.len(),
+        });
+
+        let (mut words, counts) = trainer.tokenize_words(word_counts, &mut word_to_id, &mut id_to_word, &None);
+        let mut token_counts: AHashMap<u32, u64> = AHashMap::new();
+        for (word, count) in words.iter().zip(counts.iter()) {
+            for &id in word.get_chars() {
+                *token_counts.entry(id).or_insert(0) += count;
+            }
+        }
+        trainer.emit(TrainerEvent::CountingComplete {
+            unique_words: word_counts.len(),
+        });
+
+        let mut pair_counts: AHashMap<Pair, u64> = AHashMap::new();
+        let mut where_to_update: AHashMap<Pair, HashSet<usize>> = AHashMap::new();
+        for (i, word) in words.iter().enumerate() {
+            for window in word.get_chars().windows(2) {
+                let pair: Pair = (window[0], window[1]);
+                *pair_counts.entry(pair).or_insert(0) += counts[i];
+                where_to_update.entry(pair).or_default().insert(i);
+            }
+        }
+
+        let score = |pair: Pair, count: u64, token_counts: &AHashMap<u32, u64>| -> f64 {
+            let c_a = *token_counts.get(&pair.0).unwrap_or(&1) as f64;
+            let c_b = *token_counts.get(&pair.1).unwrap_or(&1) as f64;
+            (count as f64).ln() - c_a.ln() - c_b.ln()
+        };
+
+        let mut queue: BinaryHeap<LikelihoodMerge> = pair_counts
+            .iter()
+            .filter(|(_, count)| **count >= trainer.min_frequency)
+            .map(|(pair, count)| LikelihoodMerge {
+                pair: *pair,
+                score: score(*pair, *count, &token_counts),
+                count: *count,
+                pos: where_to_update.remove(pair).unwrap_or_default(),
+            })
+            .collect();
+
+        let mut merges: Vec<(Pair, u32)> = vec![];
+        let mut merges_report: Vec<((String, String), u64)> = vec![];
+        let mut dropped_by_max_token_length = 0usize;
+        while id_to_word.len() < trainer.vocab_size {
+            let Some(mut top) = queue.pop() else {
+                break;
+            };
+
+            let current_count = *pair_counts.get(&top.pair).unwrap_or(&0);
+            if top.count != current_count {
+                if current_count >= trainer.min_frequency {
+                    top.count = current_count;
+                    top.score = score(top.pair, current_count, &token_counts);
+                    queue.push(top);
+                }
+                continue;
+            }
+            if top.count < trainer.min_frequency {
+                break;
+            }
+
+            let part_a = &id_to_word[top.pair.0 as usize];
+            let mut part_b = id_to_word[top.pair.1 as usize].clone();
+            if let Some(prefix) = &trainer.continuing_subword_prefix {
+                if let Some(without) = part_b.strip_prefix(prefix.as_str()) {
+                    part_b = without.to_string();
+                }
+            }
+            let new_token = format!("{part_a}{part_b}");
+
+            let len = new_token.chars().count();
+            let too_long = trainer.max_token_length.is_some_and(|max| len > max);
+            let too_short = trainer.min_token_length.is_some_and(|min| len < min);
+            let long_number = trainer.max_number_length.is_some_and(|max| {
+                len > max && !new_token.is_empty() && new_token.chars().all(|c| c.is_ascii_digit())
+            });
+            if too_long || too_short || long_number {
+                if too_long {
+                    dropped_by_max_token_length += 1;
+                }
+                continue;
+            }
+
+            let new_token_id = *word_to_id.entry(new_token.clone()).or_insert_with(|| {
+                id_to_word.push(new_token.clone());
+                (id_to_word.len() - 1) as u32
+            });
+            merges.push((top.pair, new_token_id));
+            let last_pair = (
+                id_to_word[top.pair.0 as usize].clone(),
+                id_to_word[top.pair.1 as usize].clone(),
+            );
+            merges_report.push((last_pair.clone(), top.count));
+            trainer.emit(TrainerEvent::MergeProgress {
+                vocab_size: id_to_word.len(),
+                merges_applied: merges.len(),
+                last_pair,
+                frequency: top.count,
+            });
+
+            let changes = top
+                .pos
+                .iter()
+                .flat_map(|&i| {
+                    let word = &mut words[i];
+                    word.merge(top.pair.0, top.pair.1, new_token_id, counts[i])
+                        .into_iter()
+                        .map(move |c| (c, i))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            *token_counts.entry(new_token_id).or_insert(0) +=
+                top.pos.iter().map(|&i| counts[i]).sum::<u64>();
+
+            for ((pair, change), iw) in changes {
+                let count = change * counts[iw] as i64;
+                let entry = pair_counts.entry(pair).or_insert(0);
+                *entry = (*entry as i64 + count).max(0) as u64;
+                if *entry > 0 {
+                    where_to_update.entry(pair).or_default().insert(iw);
+                }
+            }
+            for (pair, pos) in where_to_update.drain() {
+                let count = *pair_counts.get(&pair).unwrap_or(&0);
+                if count >= trainer.min_frequency {
+                    queue.push(LikelihoodMerge {
+                        pair,
+                        score: score(pair, count, &token_counts),
+                        count,
+                        pos,
+                    });
+                }
+            }
+        }
+
+        bpe.vocab = word_to_id.clone();
+        bpe.vocab_r = word_to_id.iter().map(|(k, v)| (*v, k.clone())).collect();
+        bpe.merges = merges
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (pair, new_id))| (pair, (rank as u32, new_id)))
+            .collect();
+
+        *model = WordPiece::from_bpe(&bpe);
+        let report = TrainingReport {
+            alphabet: alphabet_report,
+            merges: merges_report,
+            dropped_by_max_token_length,
+        };
+        Ok((trainer.special_tokens.clone(), report))
+    }
+}
+
+impl Trainer for WordPieceTrainer {
+    type Model = WordPiece;
+
+    fn should_show_progress(&self) -> bool {
+        self.bpe_trainer.should_show_progress()
+    }
+
+    fn train(&self, model: &mut WordPiece) -> Result<Vec<AddedToken>> {
+        self.do_train(&self.bpe_trainer.words(), model)
+    }
+
+    fn feed<I, S, F>(&mut self, iterator: I, process: F) -> Result<()>
+    where
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+        F: Fn(&str) -> Result<Vec<String>> + Sync,
+    {
+        self.bpe_trainer.feed(iterator, process)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_words_prefixes_continuations_with_double_hash() {
+        // `do_train_likelihood` reuses `BpeTrainer::tokenize_words` for word tokenization, which
+        // is where the "##" continuation prefix (`WordPieceTrainerBuilder`'s default
+        // `continuing_subword_prefix`) actually gets woven into the vocabulary.
+        let trainer = WordPieceTrainer::builder().vocab_size(100).build();
+        let bpe_trainer = &trainer.bpe_trainer;
+
+        let mut word_to_id: AHashMap<String, u32> = AHashMap::new();
+        let mut id_to_word: Vec<String> = Vec::new();
+        bpe_trainer.add_special_tokens(&mut word_to_id, &mut id_to_word);
+
+        let mut word_counts: AHashMap<String, u64> = AHashMap::new();
+        word_counts.insert("ab".to_string(), 1);
+        bpe_trainer.compute_alphabet(&word_counts, &mut word_to_id, &mut id_to_word);
+
+        let (words, _) =
+            bpe_trainer.tokenize_words(&word_counts, &mut word_to_id, &mut id_to_word, &None);
+
+        assert!(word_to_id.contains_key("##b"));
+        assert!(!word_to_id.contains_key("##a"));
+        assert_eq!(words[0].get_chars().len(), 2);
+    }
+}